@@ -0,0 +1,80 @@
+//! The cost of sealing and opening an envelope, before and after keys were
+//! parsed once.
+//!
+//! `keys` seals and opens with `Keys`, as the client does: the PEM keys are
+//! parsed when it is created and one PBKDF2 run yields both the AES key and
+//! IV. `per_call` is the path it replaced, built from the same primitives:
+//! the PEM key is parsed for every seal and open, and PBKDF2 runs once for
+//! the key and again for the IV. Both work on the same payload and password.
+//!
+//! Run with `cargo bench --features test-util`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kuda::{CipherSuite, DefaultSuite, Keys};
+
+const PRIVATE_KEY: &str = include_str!("../tests/fixtures/keys/private.pem");
+const PUBLIC_KEY: &str = include_str!("../tests/fixtures/keys/public.pem");
+const PASSWORD: &str = "MOCK-ABC1234";
+const PAYLOAD: &str = r#"{"serviceType":"ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE","requestRef":"SP-C0B8VKQ1N4TNAQEM9E20","data":{"trackingReference":"0012"}}"#;
+
+/// The AES key and IV derived separately, as before.
+fn derive(password: &str) -> ([u8; 32], [u8; 16]) {
+    let mut key = [0; 32];
+    DefaultSuite::pbkdf2_sha1(password.as_bytes(), b"randomsalt", 1000, &mut key).unwrap();
+    let mut iv = [0; 16];
+    DefaultSuite::pbkdf2_sha1(password.as_bytes(), b"randomsalt", 1000, &mut iv).unwrap();
+    (key, iv)
+}
+
+fn seal_per_call(password: &str, payload: &str) -> (String, String) {
+    let public_key = DefaultSuite::public_key_from_pem(PUBLIC_KEY).unwrap();
+    let (key, iv) = derive(password);
+    let data = DefaultSuite::encrypt_aes_256_cbc(payload.as_bytes(), &key, &iv).unwrap();
+    let password = DefaultSuite::rsa_encrypt(password.as_bytes(), &public_key).unwrap();
+    (base64::encode(data), base64::encode(password))
+}
+
+fn open_per_call(password: &str, data: &str) -> String {
+    let private_key = DefaultSuite::private_key_from_pem(PRIVATE_KEY).unwrap();
+    let password = base64::decode(password).unwrap();
+    let password = DefaultSuite::rsa_decrypt(&password, &private_key).unwrap();
+    let password = String::from_utf8(password).unwrap();
+    let (key, iv) = derive(password.trim_matches(char::from(0)));
+    let data = base64::decode(data).unwrap();
+    let data = DefaultSuite::decrypt_aes_256_cbc(&data, &key, &iv).unwrap();
+    String::from_utf8(data).unwrap()
+}
+
+fn envelope(c: &mut Criterion) {
+    let keys: Keys = Keys::new(PRIVATE_KEY, PUBLIC_KEY, "MOCK".to_string());
+    let (data, password) = keys.encrypt(PASSWORD, PAYLOAD).unwrap();
+    assert_eq!(open_per_call(&password, &data), PAYLOAD);
+    assert_eq!(keys.decrypt(&password, &data).unwrap(), PAYLOAD);
+
+    let mut group = c.benchmark_group("seal");
+    group.bench_function("keys", |b| {
+        b.iter(|| {
+            keys.encrypt(black_box(PASSWORD), black_box(PAYLOAD))
+                .unwrap()
+        })
+    });
+    group.bench_function("per_call", |b| {
+        b.iter(|| seal_per_call(black_box(PASSWORD), black_box(PAYLOAD)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("open");
+    group.bench_function("keys", |b| {
+        b.iter(|| {
+            keys.decrypt(black_box(&password), black_box(&data))
+                .unwrap()
+        })
+    });
+    group.bench_function("per_call", |b| {
+        b.iter(|| open_per_call(black_box(&password), black_box(&data)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, envelope);
+criterion_main!(benches);
@@ -1,21 +1,32 @@
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures::future;
 use futures::future::Future;
+use futures::stream::Stream;
 use http::header::{HeaderMap, HeaderName, HeaderValue};
 use http::request::Builder as RequestBuilder;
-use hyper_tls::HttpsConnector;
-use rand::distributions::Alphanumeric;
-use rand::Rng;
 use serde::de::DeserializeOwned;
-use std::collections::HashMap;
 
-use crate::encryption::*;
+use crate::client::connector::{default_connector, BoxConnector};
+use crate::client::retry::RetryPolicy;
+use crate::client::timeout::{within, Timeouts};
+use crate::client::token::TokenAuth;
+use crate::encryption::Keys;
+use crate::envelope::{self, Envelope, KudaResponse};
 use crate::error::{Error, RequestError};
-use crate::params::{Headers, Request};
+use crate::params::Request;
 
-type HttpClient = hyper::Client<HttpsConnector<hyper::client::HttpConnector>, hyper::Body>;
+pub(crate) type HttpClient = hyper::Client<BoxConnector, hyper::Body>;
 pub type Response<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+pub type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + Send>>;
+
+/// The path every service call is posted to when using token authentication.
+const TOKEN_API_PATH: &str = "/v2.1";
+
+/// The path bearer tokens are requested from.
+const TOKEN_PATH: &str = "/v2.1/Account/GetToken";
 
 #[allow(dead_code)]
 #[inline(always)]
@@ -33,10 +44,21 @@ pub(crate) fn err<T: Send + 'static>(err: Error) -> Response<T> {
 pub struct Client {
     host: String,
     client: HttpClient,
-    private_key: String,
-    public_key: String,
-    client_key: String,
-    headers: Headers,
+    /// Whether `client` connects through a connector passed to the builder.
+    custom_connector: bool,
+    auth: Auth,
+    headers: HeaderMap,
+    retry: Arc<RetryPolicy>,
+    timeouts: Timeouts,
+}
+
+/// How requests are authenticated with Kuda.
+#[derive(Clone)]
+pub(crate) enum Auth {
+    /// Payloads are wrapped in an RSA/AES envelope (Kuda API v1).
+    Envelope(Keys),
+    /// Plain JSON payloads with a bearer token (Kuda API v2).
+    Token(Arc<TokenAuth>),
 }
 
 impl Client {
@@ -57,68 +79,247 @@ impl Client {
         public_key: impl Into<String>,
         client_key: impl Into<String>,
     ) -> Client {
-        let host = scheme_host.into();
-        let private_key = private_key.into();
-        let public_key = public_key.into();
-        let client_key = client_key.into();
-        let https = HttpsConnector::new();
-        let client = hyper::Client::builder().build(https);
-        let headers = Headers::default();
+        let keys = Keys::new(&private_key.into(), &public_key.into(), client_key.into());
 
-        Client {
+        Client::with_auth(scheme_host.into(), Auth::Envelope(keys))
+    }
+
+    /// Creates a new client authenticated with a bearer token (Kuda API v2)
+    ///
+    /// The token is exchanged for `email` and `api_key` on first use, cached,
+    /// and refreshed transparently when it expires or is rejected. Service
+    /// calls are sent as plain JSON to the v2 API regardless of the `path`
+    /// passed to `post_body`.
+    pub fn with_api_key(
+        scheme_host: impl Into<String>,
+        email: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Client {
+        let auth = TokenAuth::new(email.into(), api_key.into());
+
+        Client::with_auth(scheme_host.into(), Auth::Token(Arc::new(auth)))
+    }
+
+    fn with_auth(host: String, auth: Auth) -> Client {
+        Client::from_parts(
             host,
-            client,
-            private_key,
-            public_key,
-            client_key,
+            None,
+            auth,
+            HeaderMap::new(),
+            RetryPolicy::none(),
+            Timeouts::default(),
+        )
+    }
+
+    /// Assembles a client, connecting through `connector` if one is given and
+    /// the default connector otherwise.
+    pub(crate) fn from_parts(
+        host: String,
+        connector: Option<BoxConnector>,
+        auth: Auth,
+        headers: HeaderMap,
+        retry: RetryPolicy,
+        timeouts: Timeouts,
+    ) -> Client {
+        Client {
+            host: host.trim_end_matches('/').to_string(),
+            custom_connector: connector.is_some(),
+            client: http_client(connector.unwrap_or_else(|| default_connector(&timeouts))),
+            auth,
             headers,
+            retry: Arc::new(retry),
+            timeouts,
+        }
+    }
+
+    /// Retry failed service calls according to `policy`
+    ///
+    /// By default each call is attempted once.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Client {
+        self.retry = Arc::new(policy);
+        self
+    }
+
+    /// Limit how long service calls may take
+    ///
+    /// Changing the connect timeout starts a new connection pool. It is not
+    /// applied to a connector passed to `ClientBuilder::connector`.
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Client {
+        if timeouts.connect != self.timeouts.connect && !self.custom_connector {
+            self.client = http_client(default_connector(&timeouts));
         }
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Limit how long each service call may take in total, retries included
+    ///
+    /// Clones share the same connection pool, so this can also be used to set
+    /// a deadline for a single call:
+    ///
+    /// ```ignore
+    /// AdminAccount::fund_transfer(&client.with_timeout(Duration::from_secs(30)), params).await?;
+    /// ```
+    pub fn with_timeout(mut self, total: Duration) -> Client {
+        self.timeouts.total = Some(total);
+        self
     }
 
     /// Make a `POST` http request with body
+    ///
+    /// Failed attempts are retried according to the client's `RetryPolicy`,
+    /// resending the same `requestRef` each time.
     pub fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Response<T>
     where
         T: DeserializeOwned + Send + 'static,
         B: serde::Serialize,
     {
-        let url = self.url(path);
+        self.post_body_with_ref(path, service_type, self.request_ref(), body)
+    }
+
+    /// Make a `POST` http request with body, sent with the given `requestRef`
+    /// so that the caller can still look the call up if it fails.
+    pub(crate) fn post_body_with_ref<T, B>(
+        &self,
+        path: &str,
+        service_type: &str,
+        request_ref: String,
+        body: B,
+    ) -> Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: serde::Serialize,
+    {
         let req = Request {
             service_type: service_type.to_string(),
-            request_ref: self.request_ref(),
+            request_ref,
             data: Some(body),
         };
-        let password = self.password();
-
-        let payload = serde_json::to_string(&req).unwrap();
-
-        let dk = make_derived_key(&password);
-        let iv = make_iv(&password);
+        let payload = match serde_json::to_string(&req) {
+            Err(error) => return err(Error::serialize(error)),
+            Ok(payload) => payload,
+        };
 
-        let encrypted_payload = encrypt_aes_256_cbc(payload.as_bytes(), &dk, &iv).unwrap();
-        let encrypted_password = rsa_encrypt(&password, &self.public_key);
+        match self.auth {
+            Auth::Envelope(ref keys) => match seal(keys, &payload) {
+                Err(error) => err(error),
+                Ok(envelope) => self.post_envelope(keys, path, req.service_type, envelope),
+            },
+            Auth::Token(ref auth) => self.post_json(auth.clone(), req.service_type, payload),
+        }
+    }
 
-        let mut request_payload: HashMap<String, String> = HashMap::new();
-        request_payload.insert("data".to_string(), base64::encode(encrypted_payload));
+    /// Make an encrypted `POST`, decrypting the response
+    fn post_envelope<T>(
+        &self,
+        keys: &Keys,
+        path: &str,
+        service_type: String,
+        envelope: Envelope,
+    ) -> Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let client = self.clone();
+        let url = self.url(path);
+        let keys = keys.clone();
+        Box::pin(async move {
+            let call = client.retry.run(&service_type, || {
+                let req = client.encrypted_request(&url, &envelope);
+                let keys = keys.clone();
+                let http = client.client.clone();
+                let timeouts = client.timeouts;
+                async move { send(&http, &timeouts, keys, req?).await }
+            });
+            within(client.timeouts.total, call).await?
+        })
+    }
 
+    /// Build the encrypted `POST` request for a service call
+    #[allow(clippy::result_large_err)]
+    fn encrypted_request(
+        &self,
+        url: &str,
+        envelope: &Envelope,
+    ) -> Result<hyper::Request<hyper::Body>, Error> {
         let mut req = RequestBuilder::new()
             .method("POST")
             .uri(url)
-            .body(match serde_json::to_string(&request_payload) {
-                Err(err) => return Box::pin(future::ready(Err(Error::serialize(err)))),
-                Ok(body) => hyper::Body::from(body),
-            })
-            .unwrap();
+            .body(hyper::Body::from(envelope.body.clone()))?;
         *req.headers_mut() = self.headers();
         req.headers_mut().insert(
             HeaderName::from_static("content-type"),
-            HeaderValue::from_str("application/json").unwrap(),
+            HeaderValue::from_static("application/json"),
         );
         req.headers_mut().insert(
             HeaderName::from_static("password"),
-            HeaderValue::from_str(&encrypted_password).unwrap(),
+            HeaderValue::from_str(&envelope.password).map_err(http::Error::from)?,
         );
 
-        send(&self.client, self.private_key.clone(), req)
+        Ok(req)
+    }
+
+    /// Make a token authenticated `POST` with a plain JSON body
+    ///
+    /// If Kuda rejects the token it is refreshed and the call retried once.
+    fn post_json<T>(
+        &self,
+        auth: Arc<TokenAuth>,
+        service_type: String,
+        payload: String,
+    ) -> Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let client = self.clone();
+        Box::pin(async move {
+            let call = client
+                .retry
+                .run(&service_type, || client.send_with_token(&auth, &payload));
+            within(client.timeouts.total, call).await?
+        })
+    }
+
+    async fn send_with_token<T>(&self, auth: &TokenAuth, payload: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let token_url = self.url(TOKEN_PATH);
+        let token = auth.token(&self.client, &self.timeouts, &token_url).await?;
+        let req = self.json_request(&token, payload.to_string())?;
+        match send_json(&self.client, &self.timeouts, req).await {
+            Err(Error::Kuda(ref error)) if error.http_status == 401 => {
+                auth.invalidate(&token).await;
+                let token = auth.token(&self.client, &self.timeouts, &token_url).await?;
+                let req = self.json_request(&token, payload.to_string())?;
+                send_json(&self.client, &self.timeouts, req).await
+            }
+            result => result,
+        }
+    }
+
+    /// Build a bearer authenticated `POST` request for the v2 API
+    #[allow(clippy::result_large_err)]
+    fn json_request(
+        &self,
+        token: &str,
+        payload: String,
+    ) -> Result<hyper::Request<hyper::Body>, Error> {
+        let mut req = RequestBuilder::new()
+            .method("POST")
+            .uri(self.url(TOKEN_API_PATH))
+            .body(hyper::Body::from(payload))?;
+        *req.headers_mut() = self.headers();
+        req.headers_mut().insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+        req.headers_mut().insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token)).map_err(http::Error::from)?,
+        );
+
+        Ok(req)
     }
 
     fn url(&self, path: &str) -> String {
@@ -126,77 +327,91 @@ impl Client {
     }
 
     fn headers(&self) -> HeaderMap {
-        HeaderMap::new()
+        self.headers.clone()
     }
 
-    fn request_ref(&self) -> String {
+    pub(crate) fn request_ref(&self) -> String {
         let g = libxid::new_generator();
         let id = g.new_id().unwrap();
 
-        let string_list = vec!["SP".to_string(), id.to_string().to_uppercase()];
+        let string_list = ["SP".to_string(), id.to_string().to_uppercase()];
         string_list.join("-")
     }
+}
 
-    fn password(&self) -> String {
-        let random: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(7)
-            .map(char::from)
-            .collect();
+fn http_client(connector: BoxConnector) -> HttpClient {
+    hyper::Client::builder().build(connector)
+}
 
-        let string_list = vec![self.client_key.clone(), random.to_uppercase()];
-        string_list.join("-")
-    }
+/// Encrypt a serialized service call
+///
+/// Built once per service call so that every attempt sends identical bytes.
+#[allow(clippy::result_large_err)]
+fn seal(keys: &Keys, payload: &str) -> Result<Envelope, Error> {
+    let (data, password) = keys.encrypt(&keys.password(), payload)?;
+
+    Ok(Envelope {
+        body: envelope::body(data)?,
+        password,
+    })
 }
 
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct KudaResponse {
-    pub data: String,
-    pub password: String,
+/// Perform `request`, returning the response body if it was successful.
+pub(crate) async fn execute(
+    client: &HttpClient,
+    timeouts: &Timeouts,
+    request: hyper::Request<hyper::Body>,
+) -> Result<hyper::body::Bytes, Error> {
+    let response = within(timeouts.request, client.request(request)).await??;
+    let status = response.status();
+    let bytes = within(timeouts.read, hyper::body::to_bytes(response.into_body())).await??;
+    if !status.is_success() {
+        let mut err = serde_json::from_slice(&bytes).unwrap_or_else(|err| RequestError {
+            message: format!("failed to deserialize error: {}", err),
+            ..Default::default()
+        });
+        err.http_status = status.as_u16();
+        return Err(Error::from(err));
+    }
+
+    Ok(bytes)
 }
 
 fn send<T>(
     client: &HttpClient,
-    private_key: String,
+    timeouts: &Timeouts,
+    keys: Keys,
     request: hyper::Request<hyper::Body>,
 ) -> Response<T>
 where
     T: DeserializeOwned + Send + 'static,
 {
     let client = client.clone(); // N.B. Client is send sync;  cloned clients share the same pool.
+    let timeouts = *timeouts;
     Box::pin(async move {
-        let response = client.request(request).await?;
-        let status = response.status();
-        let bytes = hyper::body::to_bytes(response.into_body()).await?;
-        if !status.is_success() {
-            let mut err = serde_json::from_slice(&bytes).unwrap_or_else(|err| RequestError {
-                message: format!("failed to deserialize error: {}", err),
-                ..Default::default()
-            });
-            err.http_status = status.as_u16();
-            return Err(Error::from(err));
-        }
-
-        let encrypted_response: KudaResponse = serde_json::from_slice(&bytes)
-            .map_err(Error::deserialize)
-            .unwrap();
-        let encrypted_password: String = encrypted_response.password;
-        let encrypted_data: String = encrypted_response.data;
-
-        let decrypted_password = rsa_decrypt(encrypted_password, &private_key.to_string());
-
-        let dk = make_derived_key(decrypted_password.trim_matches(char::from(0)));
-        let iv = make_iv(decrypted_password.trim_matches(char::from(0)));
+        let bytes = execute(&client, &timeouts, request).await?;
 
+        let encrypted_response: KudaResponse =
+            serde_json::from_slice(&bytes).map_err(Error::deserialize)?;
         let decrypted_data =
-            decrypt_aes_256_cbc(&base64::decode(&encrypted_data).unwrap(), &dk, &iv).unwrap();
+            keys.decrypt(&encrypted_response.password, &encrypted_response.data)?;
 
-        serde_json::from_str(
-            String::from_utf8(decrypted_data)
-                .unwrap()
-                .trim_matches(char::from(0)),
-        )
-        .map_err(Error::deserialize)
+        serde_json::from_str(&decrypted_data).map_err(Error::deserialize)
+    })
+}
+
+fn send_json<T>(
+    client: &HttpClient,
+    timeouts: &Timeouts,
+    request: hyper::Request<hyper::Body>,
+) -> Response<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let client = client.clone();
+    let timeouts = *timeouts;
+    Box::pin(async move {
+        let bytes = execute(&client, &timeouts, request).await?;
+        serde_json::from_slice(&bytes).map_err(Error::deserialize)
     })
 }
@@ -0,0 +1,173 @@
+//! A synchronous client, for batch jobs and command line tools.
+//!
+//! The blocking client wraps the async [`Client`](crate::Client) and runs its
+//! calls to completion on a runtime of its own, so sealing, retries and
+//! response decoding are exactly those of the async client. It must not be
+//! used from within an async runtime.
+//!
+//! It is a [`KudaTransport`], so resource methods return their results
+//! directly:
+//!
+//! ```ignore
+//! let client = kuda::blocking::Client::new(host, private_key, public_key, client_key);
+//! let banks = Bank::get_bank_list(&client)?;
+//! ```
+//!
+//! `Transfers::send`, `BulkTransfer::send` and the `*_transactions_stream`
+//! methods are async only, as they wait, run calls concurrently or stream.
+//! Run the first two through [`Client::call`]; page through the history
+//! methods instead of streaming.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+
+use crate::client::r#async::Client as AsyncClient;
+use crate::client::transport::KudaTransport;
+use crate::error::Error;
+
+pub type Response<T> = Result<T, Error>;
+
+#[derive(Clone)]
+pub struct Client {
+    inner: AsyncClient,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Creates a new client
+    pub fn new(
+        scheme_host: String,
+        private_key: String,
+        public_key: String,
+        client_key: String,
+    ) -> Client {
+        Client::from(AsyncClient::new(
+            scheme_host,
+            private_key,
+            public_key,
+            client_key,
+        ))
+    }
+
+    /// Creates a new client posted to a custom `scheme://host/`
+    pub fn from_url(
+        scheme_host: impl Into<String>,
+        private_key: impl Into<String>,
+        public_key: impl Into<String>,
+        client_key: impl Into<String>,
+    ) -> Client {
+        Client::from(AsyncClient::from_url(
+            scheme_host,
+            private_key,
+            public_key,
+            client_key,
+        ))
+    }
+
+    /// Creates a new client authenticated with a bearer token (Kuda API v2)
+    pub fn with_api_key(
+        scheme_host: impl Into<String>,
+        email: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Client {
+        Client::from(AsyncClient::with_api_key(scheme_host, email, api_key))
+    }
+
+    /// The async client calls are made with.
+    pub fn inner(&self) -> &AsyncClient {
+        &self.inner
+    }
+
+    /// Runs a call made with the async client to completion, for methods that
+    /// need it such as `BulkTransfer::send`:
+    ///
+    /// ```ignore
+    /// let report = client.call(|client| BulkTransfer::send(client, params))?;
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn call<T, F, Fut>(&self, call: F) -> Response<T>
+    where
+        F: FnOnce(&AsyncClient) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        self.runtime.block_on(call(&self.inner))
+    }
+
+    /// Make a `POST` http request with body
+    #[allow(clippy::result_large_err)]
+    pub fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: serde::Serialize,
+    {
+        self.call(|client| client.post_body(path, service_type, body))
+    }
+}
+
+impl KudaTransport for Client {
+    type Response<T> = Response<T>;
+
+    fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Self::Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: serde::Serialize,
+    {
+        Client::post_body(self, path, service_type, body)
+    }
+
+    fn error<T: Send + 'static>(error: Error) -> Self::Response<T> {
+        Err(error)
+    }
+}
+
+impl From<AsyncClient> for Client {
+    /// Wraps a configured async client, e.g. one made with `Client::builder()`.
+    fn from(inner: AsyncClient) -> Client {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("failed to start the blocking client's runtime");
+
+        Client {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::money::Money;
+    use crate::params::Response as R;
+    use crate::resources::*;
+
+    #[test]
+    fn test_blocking_client() {
+        let runtime = Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        server.create_virtual_account("0012", Money::from_naira(1000));
+
+        let client = Client::from(server.client());
+        let response = Bank::get_bank_list(&client).unwrap();
+        assert_eq!(response.message, "Completed Successfully");
+
+        let mut params = VirtualAccountGetRequest::new();
+        params.tracking_reference = "0012".to_string();
+        let response = VirtualAccount::get_virtual_account_balance(&client, params).unwrap();
+        assert_eq!(
+            response.data.unwrap().available_balance,
+            Money::from_naira(1000)
+        );
+
+        let response: R<GetBankListResponseData> = client
+            .call(|client| client.post_body("/v1", "BANK_LIST", ()))
+            .unwrap();
+        assert_eq!(response.data.unwrap().banks[0].bank_name, "Kuda.");
+    }
+}
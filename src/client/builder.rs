@@ -0,0 +1,354 @@
+use std::env;
+use std::sync::Arc;
+
+use http::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use hyper::client::connect::Connection;
+use hyper::service::Service;
+use hyper::Uri;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::client::connector::BoxConnector;
+use crate::client::r#async::{Auth, Client};
+use crate::client::retry::RetryPolicy;
+use crate::client::timeout::Timeouts;
+use crate::client::token::TokenAuth;
+use crate::encryption::Keys;
+use crate::error::Error;
+
+/// The user agent sent when none is configured.
+const DEFAULT_USER_AGENT: &str = concat!("kuda-rust/", env!("CARGO_PKG_VERSION"));
+
+/// A Kuda deployment to connect to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Environment {
+    /// The UAT sandbox.
+    Sandbox,
+    /// The live API.
+    Production,
+    /// Any other `scheme://host`, e.g. a local mock.
+    Custom(String),
+}
+
+impl Environment {
+    /// The `scheme://host` service calls are posted to.
+    pub fn base_url(&self) -> &str {
+        match *self {
+            Environment::Sandbox => "https://kuda-openapi-uat.kudabank.com",
+            Environment::Production => "https://kuda-openapi.kuda.com",
+            Environment::Custom(ref url) => url,
+        }
+    }
+}
+
+/// Configures and validates a [`Client`].
+///
+/// ```ignore
+/// let client = Client::builder()
+///     .environment(Environment::Production)
+///     .keys(private_key, public_key, client_key)
+///     .timeouts(Timeouts { request: Some(Duration::from_secs(30)), ..Default::default() })
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct ClientBuilder {
+    environment: Option<Environment>,
+    keys: Option<Keys>,
+    api_key: Option<(String, String)>,
+    connector: Option<BoxConnector>,
+    headers: HeaderMap,
+    user_agent: Option<String>,
+    retry: Option<RetryPolicy>,
+    timeouts: Timeouts,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Reads the configuration from `KUDA_*` environment variables.
+    ///
+    /// `KUDA_ENVIRONMENT` (`sandbox` or `production`) or `KUDA_BASE_URL`
+    /// selects the deployment, defaulting to the sandbox. Credentials are read
+    /// from `KUDA_PRIVATE_KEY`, `KUDA_PUBLIC_KEY` and `KUDA_CLIENT_KEY`, or
+    /// from `KUDA_EMAIL` and `KUDA_API_KEY` for token authentication.
+    #[allow(clippy::result_large_err)]
+    pub fn from_env() -> Result<Self, Error> {
+        let mut builder = ClientBuilder::new();
+
+        match (var("KUDA_BASE_URL"), var("KUDA_ENVIRONMENT")) {
+            (Some(url), _) => builder = builder.environment(Environment::Custom(url)),
+            (None, Some(environment)) => {
+                let environment = match environment.to_lowercase().as_str() {
+                    "sandbox" | "uat" => Environment::Sandbox,
+                    "production" | "live" => Environment::Production,
+                    _ => return Err(Error::InvalidConfig("unknown KUDA_ENVIRONMENT")),
+                };
+                builder = builder.environment(environment);
+            }
+            (None, None) => {}
+        }
+
+        match (var("KUDA_EMAIL"), var("KUDA_API_KEY")) {
+            (Some(email), Some(api_key)) => builder = builder.api_key(email, api_key),
+            _ => {
+                let private_key = var("KUDA_PRIVATE_KEY")
+                    .ok_or(Error::InvalidConfig("KUDA_PRIVATE_KEY is not set"))?;
+                let public_key = var("KUDA_PUBLIC_KEY")
+                    .ok_or(Error::InvalidConfig("KUDA_PUBLIC_KEY is not set"))?;
+                let client_key = var("KUDA_CLIENT_KEY")
+                    .ok_or(Error::InvalidConfig("KUDA_CLIENT_KEY is not set"))?;
+                builder = builder.keys(private_key, public_key, client_key);
+            }
+        }
+
+        Ok(builder)
+    }
+
+    /// The deployment to connect to. Defaults to [`Environment::Sandbox`].
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Authenticate with the RSA/AES envelope (Kuda API v1).
+    ///
+    /// `private_key` is your PEM encoded private key and `public_key` Kuda's
+    /// PEM encoded public key.
+    pub fn keys(
+        mut self,
+        private_key: impl Into<String>,
+        public_key: impl Into<String>,
+        client_key: impl Into<String>,
+    ) -> Self {
+        self.keys = Some(Keys::new(
+            &private_key.into(),
+            &public_key.into(),
+            client_key.into(),
+        ));
+        self.api_key = None;
+        self
+    }
+
+    /// Authenticate with a bearer token exchanged for an API key (Kuda API v2).
+    pub fn api_key(mut self, email: impl Into<String>, api_key: impl Into<String>) -> Self {
+        self.api_key = Some((email.into(), api_key.into()));
+        self.keys = None;
+        self
+    }
+
+    /// Connect through `connector` instead of the default one.
+    ///
+    /// Any `hyper` connector can be used, e.g. one that tunnels through a
+    /// proxy, presents a client certificate or connects in-process. The
+    /// connect timeout is not applied to a custom connector; configure it on
+    /// the connector instead.
+    pub fn connector<C>(mut self, connector: C) -> Self
+    where
+        C: Service<Uri> + Clone + Send + Sync + 'static,
+        C::Response: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+        C::Future: Send + 'static,
+        C::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.connector = Some(BoxConnector::new(connector));
+        self
+    }
+
+    /// A header sent with every request.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// The `User-Agent` sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Validates the configuration and creates the client.
+    ///
+    /// Fails with `Error::InvalidKey` if either RSA key cannot be parsed.
+    #[allow(clippy::result_large_err)]
+    pub fn build(self) -> Result<Client, Error> {
+        let auth = match (self.keys, self.api_key) {
+            (Some(keys), _) => {
+                keys.validate()?;
+                Auth::Envelope(keys)
+            }
+            (None, Some((email, api_key))) => Auth::Token(Arc::new(TokenAuth::new(email, api_key))),
+            (None, None) => return Err(Error::InvalidConfig("no credentials configured")),
+        };
+
+        let user_agent = self
+            .user_agent
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        let mut headers = self.headers;
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_str(&user_agent)
+                .map_err(|_| Error::InvalidConfig("invalid user agent"))?,
+        );
+
+        let host = self
+            .environment
+            .unwrap_or(Environment::Sandbox)
+            .base_url()
+            .to_string();
+
+        Ok(Client::from_parts(
+            host,
+            self.connector,
+            auth,
+            headers,
+            self.retry.unwrap_or_else(RetryPolicy::none),
+            self.timeouts,
+        ))
+    }
+}
+
+impl Client {
+    /// Configure a client with a [`ClientBuilder`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Creates a client configured from `KUDA_*` environment variables.
+    ///
+    /// See [`ClientBuilder::from_env`].
+    #[allow(clippy::result_large_err)]
+    pub fn from_env() -> Result<Client, Error> {
+        ClientBuilder::from_env()?.build()
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll};
+
+    use hyper::client::HttpConnector;
+
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::resources::*;
+
+    /// A connector that counts the connections it makes.
+    #[derive(Clone)]
+    struct Counting {
+        http: HttpConnector,
+        connects: Arc<AtomicUsize>,
+    }
+
+    impl Service<Uri> for Counting {
+        type Response = <HttpConnector as Service<Uri>>::Response;
+        type Error = <HttpConnector as Service<Uri>>::Error;
+        type Future = <HttpConnector as Service<Uri>>::Future;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.http.poll_ready(cx)
+        }
+
+        fn call(&mut self, uri: Uri) -> Self::Future {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+            self.http.call(uri)
+        }
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_keys() {
+        let result = Client::builder()
+            .keys("CHANGE_ME", "CHANGE_ME", "CHANGE_ME")
+            .build();
+        match result {
+            Err(Error::InvalidKey(_)) => {}
+            Err(other) => panic!("{:?}", other),
+            Ok(_) => panic!("built a client with invalid keys"),
+        }
+
+        match Client::builder().build() {
+            Err(Error::InvalidConfig(_)) => {}
+            Err(other) => panic!("{:?}", other),
+            Ok(_) => panic!("built a client without credentials"),
+        }
+    }
+
+    #[test]
+    fn test_environment() {
+        assert_eq!(
+            Environment::Sandbox.base_url(),
+            "https://kuda-openapi-uat.kudabank.com"
+        );
+        assert_eq!(
+            Environment::Custom("http://127.0.0.1:8080".to_string()).base_url(),
+            "http://127.0.0.1:8080"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build() {
+        let server = MockServer::start().await;
+        let client = Client::builder()
+            .environment(Environment::Custom(server.url()))
+            .keys(server.private_key(), server.public_key(), "MOCK")
+            .default_header(
+                HeaderName::from_static("x-merchant"),
+                HeaderValue::from_static("seampay"),
+            )
+            .build()
+            .unwrap();
+
+        let response = Bank::get_bank_list(&client).await.unwrap();
+        assert_eq!(response.message, "Completed Successfully");
+
+        let headers = server.request_headers();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0]["x-merchant"], "seampay");
+        assert_eq!(headers[0][USER_AGENT], DEFAULT_USER_AGENT);
+
+        let client = Client::builder()
+            .environment(Environment::Custom(server.url()))
+            .keys(server.private_key(), server.public_key(), "MOCK")
+            .user_agent("seampay/2.0")
+            .build()
+            .unwrap();
+        Bank::get_bank_list(&client).await.unwrap();
+        assert_eq!(server.request_headers()[1][USER_AGENT], "seampay/2.0");
+    }
+
+    #[tokio::test]
+    async fn test_custom_connector() {
+        let server = MockServer::start().await;
+        let connects = Arc::new(AtomicUsize::new(0));
+        let client = Client::builder()
+            .environment(Environment::Custom(server.url()))
+            .keys(server.private_key(), server.public_key(), "MOCK")
+            .connector(Counting {
+                http: HttpConnector::new(),
+                connects: connects.clone(),
+            })
+            .build()
+            .unwrap()
+            .with_timeouts(Timeouts {
+                connect: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
+            });
+
+        Bank::get_bank_list(&client).await.unwrap();
+        assert_eq!(connects.load(Ordering::SeqCst), 1);
+    }
+}
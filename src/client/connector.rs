@@ -0,0 +1,141 @@
+use std::error::Error as StdError;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use hyper::service::Service;
+use hyper::Uri;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::client::timeout::Timeouts;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("kuda needs TLS to reach Kuda: enable the `native-tls` or `rustls` feature");
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+type Connecting = Pin<Box<dyn Future<Output = Result<Conn, BoxError>> + Send>>;
+
+/// A connection made by any connector.
+pub(crate) trait Io: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static {}
+
+impl<T> Io for T where T: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static {}
+
+/// A type-erased `hyper::client::connect::Connect`, so `Client` keeps a
+/// single type whichever connector it was built with.
+#[derive(Clone)]
+pub(crate) struct BoxConnector {
+    connect: Arc<dyn Fn(Uri) -> Connecting + Send + Sync>,
+}
+
+impl BoxConnector {
+    pub(crate) fn new<C>(connector: C) -> BoxConnector
+    where
+        C: Service<Uri> + Clone + Send + Sync + 'static,
+        C::Response: Io,
+        C::Future: Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        let connect = move |uri: Uri| -> Connecting {
+            let mut connector = connector.clone();
+            Box::pin(async move {
+                futures::future::poll_fn(|cx| connector.poll_ready(cx))
+                    .await
+                    .map_err(Into::into)?;
+                let io = connector.call(uri).await.map_err(Into::into)?;
+                Ok(Conn(Box::new(io)))
+            })
+        };
+
+        BoxConnector {
+            connect: Arc::new(connect),
+        }
+    }
+}
+
+impl Service<Uri> for BoxConnector {
+    type Response = Conn;
+    type Error = BoxError;
+    type Future = Connecting;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        (self.connect)(uri)
+    }
+}
+
+/// A connection made by a [`BoxConnector`].
+pub(crate) struct Conn(Box<dyn Io>);
+
+impl Connection for Conn {
+    fn connected(&self) -> Connected {
+        self.0.connected()
+    }
+}
+
+impl AsyncRead for Conn {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Conn {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_shutdown(cx)
+    }
+}
+
+/// The connector used unless another is configured: TLS from `hyper-tls` with
+/// the `native-tls` feature, otherwise from `hyper-rustls` with the `rustls`
+/// feature.
+pub(crate) fn default_connector(timeouts: &Timeouts) -> BoxConnector {
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    http.set_connect_timeout(timeouts.connect);
+
+    #[cfg(feature = "native-tls")]
+    return BoxConnector::new(hyper_tls::HttpsConnector::new_with_connector(http));
+
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    return BoxConnector::new(
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http),
+    );
+}
@@ -0,0 +1,274 @@
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::error::{Error, HttpError};
+
+/// Service types that only read or enquire, and so are safe to repeat.
+const IDEMPOTENT: &[&str] = &[
+    "ADMIN_GET_PURCHASED_BILLS",
+    "ADMIN_MAIN_ACCOUNT_FILTERED_TRANSACTIONS",
+    "ADMIN_MAIN_ACCOUNT_TRANSACTIONS",
+    "ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE",
+    "ADMIN_RETRIEVE_SINGLE_VIRTUAL_ACCOUNT",
+    "ADMIN_VIRTUAL_ACCOUNTS",
+    "ADMIN_VIRTUAL_ACCOUNT_FILTERED_TRANSACTIONS",
+    "ADMIN_VIRTUAL_ACCOUNT_TRANSACTIONS",
+    "BANK_LIST",
+    "BILL_TSQ",
+    "FIXED_SAVE_TRANSACTIONS",
+    "GET_BILLERS_BY_TYPE",
+    "GET_CUSTOMER_CARDS",
+    "GET_FIXED_SAVE",
+    "GET_OPEN_FLEXIBLE_SAVE",
+    "GET_PLAIN_SAVINGS",
+    "NAME_ENQUIRY",
+    "OPEN_FLEXIBLE_SAVE_TRANSACTIONS",
+    "PLAIN_SAVINGS_TRANSACTIONS",
+    "RETRIEVE_VIRTUAL_ACCOUNT_BALANCE",
+    "TRANSACTION_STATUS_QUERY",
+    "VERIFY_BILL_CUSTOMER",
+];
+
+/// Whether a failed service call may be attempted again, and when.
+///
+/// Every attempt of a call is sent with the same `requestRef`, so Kuda can
+/// recognise a repeat of a request it has already processed. Still, only
+/// calls that read or enquire are retried, unless `retry_non_idempotent` is
+/// set; transfers, purchases, account changes and any service type this crate
+/// does not know are attempted once.
+///
+/// ```ignore
+/// let client = Client::new(host, private_key, public_key, client_key)
+///     .with_retry_policy(RetryPolicy::default());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each attempt.
+    pub multiplier: u32,
+    /// Randomise each delay between half and all of its nominal value.
+    pub jitter: bool,
+    /// HTTP statuses worth retrying.
+    pub retry_statuses: Vec<u16>,
+    /// Retry connection failures, timeouts and interrupted responses.
+    pub retry_network_errors: bool,
+    /// Also retry transfers, purchases and other calls that change state.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            retry_statuses: vec![408, 429, 500, 502, 503, 504],
+            retry_network_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that makes exactly one attempt.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Whether `error` from a call to `service_type` is worth another attempt.
+    pub fn is_retryable(&self, service_type: &str, error: &Error) -> bool {
+        if !self.retry_non_idempotent && !IDEMPOTENT.contains(&service_type) {
+            return false;
+        }
+
+        match *error {
+            Error::Kuda(ref err) => self.retry_statuses.contains(&err.http_status),
+            Error::Http(HttpError::Stream(_)) | Error::Http(HttpError::Timeout) | Error::Io(_) => {
+                self.retry_network_errors
+            }
+            _ => false,
+        }
+    }
+
+    /// The delay before attempt number `attempt + 1`, counting from one.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1)
+            .saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter && delay > Duration::from_millis(1) {
+            let half = delay / 2;
+            half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+
+    /// Runs `attempt` until it succeeds, fails with an error that is not
+    /// retryable, or runs out of attempts.
+    pub(crate) async fn run<T, F, Fut>(
+        &self,
+        service_type: &str,
+        mut attempt: F,
+    ) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempts = 1;
+        loop {
+            let result = attempt().await;
+            let retry = match result {
+                Err(ref error) => {
+                    attempts < self.max_attempts && self.is_retryable(service_type, error)
+                }
+                Ok(_) => false,
+            };
+            if !retry {
+                return result;
+            }
+
+            tokio::time::sleep(self.backoff(attempts)).await;
+            attempts += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RequestError;
+    use crate::mock::MockServer;
+    use crate::money::Money;
+    use crate::resources::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            jitter: false,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(40), Duration::from_millis(300));
+
+        let jittered = RetryPolicy {
+            jitter: true,
+            ..policy
+        }
+        .backoff(2);
+        assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let unavailable = Error::from(RequestError {
+            http_status: 503,
+            ..Default::default()
+        });
+        let rejected = Error::from(RequestError {
+            http_status: 400,
+            ..Default::default()
+        });
+
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable("NAME_ENQUIRY", &unavailable));
+        assert!(policy.is_retryable("NAME_ENQUIRY", &Error::timeout()));
+        assert!(!policy.is_retryable("NAME_ENQUIRY", &rejected));
+        assert!(!policy.is_retryable("SINGLE_FUND_TRANSFER", &unavailable));
+        assert!(!policy.is_retryable("CREDIT_DEBIT_PLAIN_SAVINGS", &unavailable));
+        assert!(!policy.is_retryable("SOME_NEW_SERVICE", &unavailable));
+
+        let policy = RetryPolicy {
+            retry_non_idempotent: true,
+            ..policy
+        };
+        assert!(policy.is_retryable("SINGLE_FUND_TRANSFER", &unavailable));
+    }
+
+    #[tokio::test]
+    async fn test_retry_reuses_request_ref() {
+        let server = MockServer::start().await;
+        let client = server.client().with_retry_policy(policy());
+        server.fail(
+            "ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE",
+            503,
+            "Service unavailable",
+        );
+        server.fail("ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE", 502, "Bad gateway");
+
+        let response =
+            AdminAccount::get_admin_account_balance(&client, AdminAccountBalanceRequest::new())
+                .await
+                .unwrap();
+        assert!(response.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|request| request.request_ref == requests[0].request_ref));
+    }
+
+    #[tokio::test]
+    async fn test_retry_non_idempotent() {
+        let transfer = || {
+            let mut params = AdminAccountFundTransferRequest::new();
+            params.beneficiary_account = "0123456789".to_string();
+            params.beneficiary_bank_code = "000013".to_string();
+            params.amount = Money::from_naira(100);
+            params
+        };
+
+        let server = MockServer::start().await;
+        server.fail("SINGLE_FUND_TRANSFER", 503, "Service unavailable");
+        let client = server.client().with_retry_policy(policy());
+        match AdminAccount::fund_transfer(&client, transfer()).await {
+            Err(Error::Kuda(err)) => assert_eq!(err.http_status, 503),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(server.requests().len(), 1);
+
+        let server = MockServer::start().await;
+        server.fail("SINGLE_FUND_TRANSFER", 503, "Service unavailable");
+        let client = server.client().with_retry_policy(RetryPolicy {
+            retry_non_idempotent: true,
+            ..policy()
+        });
+        let response = AdminAccount::fund_transfer(&client, transfer())
+            .await
+            .unwrap();
+        assert!(response.status);
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,137 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// Time limits for service calls.
+///
+/// By default connecting is limited to 10 seconds and a whole call to 60
+/// seconds, so a hung endpoint cannot stall a caller forever. Unset limits
+/// never fire. When one does fire the call fails with `HttpError::Timeout`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit on establishing a connection to Kuda.
+    pub connect: Option<Duration>,
+    /// Limit on sending a request and receiving the response headers.
+    pub request: Option<Duration>,
+    /// Limit on reading the response body.
+    pub read: Option<Duration>,
+    /// Limit on the whole call, including token refreshes, retries and the
+    /// delays between them.
+    pub total: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            connect: Some(Duration::from_secs(10)),
+            request: None,
+            read: None,
+            total: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+impl Timeouts {
+    /// Limits that never fire.
+    pub fn none() -> Self {
+        Timeouts {
+            connect: None,
+            request: None,
+            read: None,
+            total: None,
+        }
+    }
+}
+
+/// Awaits `future`, failing with a timeout if it takes longer than `limit`.
+pub(crate) async fn within<F: Future>(
+    limit: Option<Duration>,
+    future: F,
+) -> Result<F::Output, Error> {
+    match limit {
+        None => Ok(future.await),
+        Some(limit) => tokio::time::timeout(limit, future)
+            .await
+            .map_err(|_| Error::timeout()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::HttpError;
+    use crate::mock::MockServer;
+    use crate::resources::*;
+    use crate::RetryPolicy;
+
+    const SLOW: Duration = Duration::from_secs(2);
+
+    fn assert_timeout<T: std::fmt::Debug>(result: Result<T, Error>) {
+        match result {
+            Err(Error::Http(HttpError::Timeout)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_timeouts() {
+        let timeouts = Timeouts::default();
+        assert_eq!(timeouts.connect, Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.total, Some(Duration::from_secs(60)));
+        assert_eq!(Timeouts::none().total, None);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start().await;
+        server.delay("ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE", SLOW);
+        let client = server.client();
+        // Only the delayed call gets the short limit; sealing a request can
+        // itself take longer than that in debug builds.
+        let impatient = client.clone().with_timeouts(Timeouts {
+            request: Some(Duration::from_millis(100)),
+            ..Default::default()
+        });
+
+        assert_timeout(
+            AdminAccount::get_admin_account_balance(&impatient, AdminAccountBalanceRequest::new())
+                .await,
+        );
+
+        let response =
+            AdminAccount::get_admin_account_balance(&client, AdminAccountBalanceRequest::new())
+                .await
+                .unwrap();
+        assert!(response.status);
+    }
+
+    #[tokio::test]
+    async fn test_total_timeout_spans_retries() {
+        let server = MockServer::start().await;
+        for _ in 0..3 {
+            server.delay("ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE", SLOW);
+        }
+        let client = server
+            .client()
+            .with_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+            .with_timeouts(Timeouts {
+                request: Some(Duration::from_millis(100)),
+                ..Default::default()
+            });
+
+        let started = std::time::Instant::now();
+        assert_timeout(
+            AdminAccount::get_admin_account_balance(
+                &client.with_timeout(Duration::from_millis(150)),
+                AdminAccountBalanceRequest::new(),
+            )
+            .await,
+        );
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(server.requests().len() <= 2);
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use http::header::{HeaderName, HeaderValue};
+use http::request::Builder as RequestBuilder;
+use serde_derive::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::client::r#async::{execute, HttpClient};
+use crate::client::timeout::Timeouts;
+use crate::error::Error;
+
+/// Tokens are refreshed this long before they expire.
+const EXPIRY_SKEW: Duration = Duration::from_secs(30);
+
+/// Lifetime assumed for tokens whose expiry cannot be read.
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// Bearer token credentials for the Kuda v2 API.
+///
+/// The token is fetched lazily, shared by every clone of the client and
+/// refreshed when it is about to expire or has been rejected by Kuda.
+pub(crate) struct TokenAuth {
+    email: String,
+    api_key: String,
+    cached: Mutex<Option<Token>>,
+}
+
+struct Token {
+    value: String,
+    expires_at: Instant,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenRequest<'a> {
+    email: &'a str,
+    api_key: &'a str,
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+    exp: Option<u64>,
+}
+
+impl TokenAuth {
+    pub(crate) fn new(email: String, api_key: String) -> TokenAuth {
+        TokenAuth {
+            email,
+            api_key,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Returns a valid bearer token, fetching a new one from `url` if needed.
+    pub(crate) async fn token(
+        &self,
+        client: &HttpClient,
+        timeouts: &Timeouts,
+        url: &str,
+    ) -> Result<String, Error> {
+        let mut cached = self.cached.lock().await;
+        if let Some(ref token) = *cached {
+            if Instant::now() + EXPIRY_SKEW < token.expires_at {
+                return Ok(token.value.clone());
+            }
+        }
+
+        let token = self.fetch(client, timeouts, url).await?;
+        let value = token.value.clone();
+        *cached = Some(token);
+        Ok(value)
+    }
+
+    /// Discards the cached token if it is the one Kuda just `rejected`.
+    pub(crate) async fn invalidate(&self, rejected: &str) {
+        let mut cached = self.cached.lock().await;
+        let stale = match *cached {
+            Some(ref token) => token.value == rejected,
+            None => false,
+        };
+        if stale {
+            *cached = None;
+        }
+    }
+
+    async fn fetch(
+        &self,
+        client: &HttpClient,
+        timeouts: &Timeouts,
+        url: &str,
+    ) -> Result<Token, Error> {
+        let body = serde_json::to_string(&TokenRequest {
+            email: &self.email,
+            api_key: &self.api_key,
+        })
+        .map_err(Error::serialize)?;
+
+        let mut req = RequestBuilder::new()
+            .method("POST")
+            .uri(url)
+            .body(hyper::Body::from(body))?;
+        req.headers_mut().insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let bytes = execute(client, timeouts, req).await?;
+
+        // The token is returned either as a bare string or a JSON string.
+        let value = match serde_json::from_slice::<String>(&bytes) {
+            Ok(value) => value,
+            Err(_) => String::from_utf8(bytes.to_vec()).map_err(Error::deserialize)?,
+        };
+        let value = value.trim().to_string();
+        if value.is_empty() {
+            return Err(Error::Unexpected("kuda returned an empty token"));
+        }
+
+        let expires_at = Instant::now() + lifetime(&value).unwrap_or(DEFAULT_LIFETIME);
+        Ok(Token { value, expires_at })
+    }
+}
+
+/// Reads the remaining lifetime from the `exp` claim of a JWT.
+fn lifetime(token: &str) -> Option<Duration> {
+    let claims = token.split('.').nth(1)?;
+    let claims = base64::decode_config(claims, base64::URL_SAFE_NO_PAD).ok()?;
+    let exp = serde_json::from_slice::<TokenClaims>(&claims).ok()?.exp?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(exp.saturating_sub(now)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifetime_from_jwt() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let claims = format!("{{\"exp\":{}}}", now + 600);
+        let token = format!(
+            "e30.{}.c2lnbmF0dXJl",
+            base64::encode_config(claims, base64::URL_SAFE_NO_PAD)
+        );
+
+        let lifetime = lifetime(&token).unwrap();
+        assert!(lifetime <= Duration::from_secs(600));
+        assert!(lifetime > Duration::from_secs(590));
+    }
+
+    #[test]
+    fn test_lifetime_from_opaque_token() {
+        assert_eq!(lifetime("not-a-jwt"), None);
+    }
+}
@@ -0,0 +1,122 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::r#async::{self, Client};
+use crate::error::Error;
+
+/// Sends service calls to Kuda.
+///
+/// Every resource method takes any `KudaTransport`, so calls can be recorded,
+/// mocked, made with the [blocking client](crate::blocking) or made over
+/// another HTTP stack. A transport that talks to Kuda itself can seal requests
+/// and open responses with [`envelope`](crate::envelope).
+///
+/// ```ignore
+/// impl KudaTransport for Recorder {
+///     type Response<T> = kuda::Response<T>;
+///
+///     fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Self::Response<T>
+///     where
+///         T: DeserializeOwned + Send + 'static,
+///         B: Serialize,
+///     {
+///         self.calls.lock().unwrap().push(service_type.to_string());
+///         self.client.post_body(path, service_type, body)
+///     }
+///
+///     fn error<T: Send + 'static>(error: Error) -> Self::Response<T> {
+///         Client::error(error)
+///     }
+/// }
+/// ```
+pub trait KudaTransport {
+    /// What a call returns, e.g. a future for the async client and the
+    /// result itself for the blocking one.
+    type Response<T>;
+
+    /// Posts `body` as the data of a `service_type` request to `path` and
+    /// decodes the response.
+    fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Self::Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: Serialize;
+
+    /// A response that fails with `error` without making a call, for
+    /// requests rejected before they are sent.
+    fn error<T: Send + 'static>(error: Error) -> Self::Response<T>;
+}
+
+impl KudaTransport for Client {
+    type Response<T> = r#async::Response<T>;
+
+    fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Self::Response<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: Serialize,
+    {
+        Client::post_body(self, path, service_type, body)
+    }
+
+    fn error<T: Send + 'static>(error: Error) -> Self::Response<T> {
+        r#async::err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::resources::*;
+
+    struct Recorder {
+        client: Client,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl KudaTransport for Recorder {
+        type Response<T> = r#async::Response<T>;
+
+        fn post_body<T, B>(&self, path: &str, service_type: &str, body: B) -> Self::Response<T>
+        where
+            T: DeserializeOwned + Send + 'static,
+            B: Serialize,
+        {
+            self.calls.lock().unwrap().push(service_type.to_string());
+            self.client.post_body(path, service_type, body)
+        }
+
+        fn error<T: Send + 'static>(error: Error) -> Self::Response<T> {
+            Client::error(error)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() {
+        let server = MockServer::start().await;
+        let recorder = Recorder {
+            client: server.client(),
+            calls: Mutex::new(Vec::new()),
+        };
+
+        let response = Bank::get_bank_list(&recorder).await.unwrap();
+        assert_eq!(response.message, "Completed Successfully");
+        AdminAccount::get_admin_account_balance(&recorder, AdminAccountBalanceRequest::new())
+            .await
+            .unwrap();
+
+        let mut params = SavingsFundWithdrawRequest::new();
+        params.tracking_reference = "0012".to_string();
+        match Savings::fund_savings_account(&recorder, SavingsProduct::Fixed, params).await {
+            Err(Error::Unsupported(_)) => {}
+            other => panic!("expected Error::Unsupported, got {:?}", other),
+        }
+
+        assert_eq!(
+            *recorder.calls.lock().unwrap(),
+            vec!["BANK_LIST", "ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE"]
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,125 @@
+//! Dates and times in the format Kuda uses.
+//!
+//! Kuda sends and expects times like `2021-05-28T14:45:12.893`: ISO 8601
+//! without an offset, with optional fractional seconds. They are kept as
+//! `chrono::NaiveDateTime`; use [`naive`] or [`option`] as serde adapters for
+//! such fields.
+
+use std::ops::RangeInclusive;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, ParseResult};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::Serializer;
+
+/// The format times are sent in. Fractional seconds are only written when
+/// the time has them.
+const FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Parses a time as Kuda sends it.
+pub fn parse(s: &str) -> ParseResult<NaiveDateTime> {
+    let s = s.trim();
+    NaiveDateTime::parse_from_str(s, FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f"))
+}
+
+/// Formats a time as Kuda expects it.
+pub fn format(time: &NaiveDateTime) -> String {
+    time.format(FORMAT).to_string()
+}
+
+/// The first and last millisecond of a range of days, as used by the filtered
+/// transaction history requests. Kuda's times carry milliseconds, so the end
+/// bound does too.
+pub(crate) fn bounds(dates: RangeInclusive<NaiveDate>) -> (NaiveDateTime, NaiveDateTime) {
+    let (start, end) = dates.into_inner();
+    let start_of_day = NaiveTime::from_hms_opt(0, 0, 0).expect("invalid start of day");
+    let end_of_day = NaiveTime::from_hms_milli_opt(23, 59, 59, 999).expect("invalid end of day");
+    (start.and_time(start_of_day), end.and_time(end_of_day))
+}
+
+/// Serde adapter for times in Kuda's format.
+///
+/// ```ignore
+/// #[serde(rename = "RealDate", with = "crate::datetime::naive")]
+/// pub real_date: NaiveDateTime,
+/// ```
+pub mod naive {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &NaiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format(FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NaiveDateTime, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse(&s).map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &"a Kuda date"))
+    }
+}
+
+/// Serde adapter for optional times in Kuda's format. Missing, `null` and
+/// empty times are `None`.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match *time {
+            Some(ref time) => super::naive::serialize(time, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(ref s) if !s.trim().is_empty() => parse(s)
+                .map(Some)
+                .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(s), &"a Kuda date")),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let time = date(2021, 5, 28)
+            .and_hms_milli_opt(14, 45, 12, 893)
+            .unwrap();
+        assert_eq!(parse("2021-05-28T14:45:12.893").unwrap(), time);
+        assert_eq!(parse("2021-05-28 14:45:12.893").unwrap(), time);
+        assert_eq!(format(&time), "2021-05-28T14:45:12.893");
+
+        let time = date(2021, 5, 24).and_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(parse("2021-05-24T00:00:00").unwrap(), time);
+        assert_eq!(format(&time), "2021-05-24T00:00:00");
+
+        assert!(parse("24/05/2021").is_err());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let (start, end) = bounds(date(2021, 5, 24)..=date(2021, 5, 25));
+        assert_eq!(format(&start), "2021-05-24T00:00:00");
+        assert_eq!(format(&end), "2021-05-25T23:59:59.999");
+
+        let last_second = parse("2021-05-25T23:59:59.5").unwrap();
+        assert!(start <= last_second && last_second <= end);
+        assert!(parse("2021-05-26T00:00:00").unwrap() > end);
+    }
+}
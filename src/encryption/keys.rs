@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use super::{CipherSuite, DefaultSuite, DerivedKey};
+use crate::error::{CryptoError, EnvelopeError, Error};
+
+/// The keys used to seal requests to Kuda and open its responses.
+///
+/// The PEM keys are parsed once, when `Keys` is created, and shared by every
+/// clone. A key that could not be parsed fails each call that needs it with
+/// `Error::InvalidKey`.
+pub struct Keys<S: CipherSuite = DefaultSuite> {
+    /// Each parsed key, or why it failed to parse.
+    private_key: Arc<Result<S::PrivateKey, CryptoError>>,
+    public_key: Arc<Result<S::PublicKey, CryptoError>>,
+    client_key: String,
+}
+
+impl<S: CipherSuite> Clone for Keys<S> {
+    fn clone(&self) -> Self {
+        Keys {
+            private_key: self.private_key.clone(),
+            public_key: self.public_key.clone(),
+            client_key: self.client_key.clone(),
+        }
+    }
+}
+
+impl<S: CipherSuite> Keys<S> {
+    /// `private_key` is your PEM encoded private key and `public_key` Kuda's
+    /// PEM encoded public key.
+    pub fn new(private_key: &str, public_key: &str, client_key: String) -> Keys<S> {
+        Keys {
+            private_key: Arc::new(S::private_key_from_pem(private_key)),
+            public_key: Arc::new(S::public_key_from_pem(public_key)),
+            client_key,
+        }
+    }
+
+    /// Checks that both keys parsed and the client key is set.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        self.private_key()?;
+        self.public_key()?;
+        if self.client_key.is_empty() {
+            return Err(Error::InvalidConfig("client key is empty"));
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn private_key(&self) -> Result<&S::PrivateKey, Error> {
+        match *self.private_key {
+            Ok(ref key) => Ok(key),
+            Err(ref err) => Err(Error::InvalidKey(err.clone())),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn public_key(&self) -> Result<&S::PublicKey, Error> {
+        match *self.public_key {
+            Ok(ref key) => Ok(key),
+            Err(ref err) => Err(Error::InvalidKey(err.clone())),
+        }
+    }
+
+    /// A fresh envelope password.
+    pub fn password(&self) -> String {
+        password(&self.client_key)
+    }
+
+    /// Encrypts `payload` with `password` for Kuda.
+    #[allow(clippy::result_large_err)]
+    pub fn encrypt(&self, password: &str, payload: &str) -> Result<(String, String), Error> {
+        encrypt::<S>(self.public_key()?, password, payload)
+    }
+
+    /// Decrypts a payload Kuda sealed for us.
+    #[allow(clippy::result_large_err)]
+    pub fn decrypt(&self, password: &str, data: &str) -> Result<String, Error> {
+        decrypt::<S>(self.private_key()?, password, data)
+    }
+}
+
+/// A fresh envelope password: the client key and a random suffix.
+pub(crate) fn password(client_key: &str) -> String {
+    let random: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(7)
+        .map(char::from)
+        .collect();
+
+    format!("{}-{}", client_key, random.to_uppercase())
+}
+
+/// Encrypts `payload` with `password`, returning the base64 encoded
+/// ciphertext and the base64 encoded, RSA encrypted password.
+#[allow(clippy::result_large_err)]
+pub(crate) fn encrypt<S: CipherSuite>(
+    public_key: &S::PublicKey,
+    password: &str,
+    payload: &str,
+) -> Result<(String, String), Error> {
+    let derived = DerivedKey::derive::<S>(password)?;
+
+    let data = S::encrypt_aes_256_cbc(payload.as_bytes(), &derived.key, &derived.iv)?;
+    let password = S::rsa_encrypt(password.as_bytes(), public_key)?;
+
+    Ok((base64::encode(data), base64::encode(password)))
+}
+
+/// Decrypts base64 encoded `data` sealed with the RSA encrypted `password`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn decrypt<S: CipherSuite>(
+    private_key: &S::PrivateKey,
+    password: &str,
+    data: &str,
+) -> Result<String, Error> {
+    let password = base64::decode(password).map_err(EnvelopeError::from)?;
+    let password = S::rsa_decrypt(&password, private_key)?;
+    let password = String::from_utf8(password).map_err(EnvelopeError::from)?;
+    let derived = DerivedKey::derive::<S>(password.trim_matches(char::from(0)))?;
+
+    let data = base64::decode(data).map_err(EnvelopeError::from)?;
+    let data = S::decrypt_aes_256_cbc(&data, &derived.key, &derived.iv)?;
+    let data = String::from_utf8(data).map_err(EnvelopeError::from)?;
+
+    Ok(data.trim_matches(char::from(0)).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_key() {
+        let keys: Keys = Keys::new("CHANGE_ME", "CHANGE_ME", "CHANGE_ME".to_string());
+        match keys.encrypt("password", "{}") {
+            Err(Error::InvalidKey(_)) => {}
+            other => panic!("expected Error::InvalidKey, got {:?}", other),
+        }
+        match keys.decrypt("cGFzc3dvcmQ=", "") {
+            Err(Error::InvalidKey(_)) => {}
+            other => panic!("expected Error::InvalidKey, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let keys: Keys = Keys::new(
+            include_str!("../../tests/fixtures/keys/private.pem"),
+            include_str!("../../tests/fixtures/keys/public.pem"),
+            "MOCK".to_string(),
+        );
+
+        let password = keys.password();
+        assert!(password.starts_with("MOCK-"));
+        let (data, password) = keys
+            .encrypt(&password, r#"{"serviceType":"BANK_LIST"}"#)
+            .unwrap();
+        assert_eq!(
+            keys.decrypt(&password, &data).unwrap(),
+            r#"{"serviceType":"BANK_LIST"}"#
+        );
+    }
+}
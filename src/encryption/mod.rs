@@ -1,5 +1,154 @@
-pub use self::aes::{decrypt_aes_256_cbc, encrypt_aes_256_cbc, make_derived_key, make_iv};
-pub use self::rsa::{rsa_decrypt, rsa_encrypt};
+pub use self::keys::Keys;
+pub(crate) use self::keys::{decrypt, encrypt, password};
+#[cfg(feature = "openssl")]
+pub use self::openssl::OpenSsl;
+#[cfg(feature = "rustcrypto")]
+pub use self::rustcrypto::RustCrypto;
 
-mod aes;
-mod rsa;
+use crate::error::CryptoError;
+
+mod keys;
+#[cfg(feature = "openssl")]
+mod openssl;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
+
+#[cfg(not(any(feature = "openssl", feature = "rustcrypto")))]
+compile_error!("kuda needs a cipher suite: enable the `openssl` or `rustcrypto` feature");
+
+/// The cipher suite used by `Client`: OpenSSL when the `openssl` feature is
+/// enabled, otherwise RustCrypto.
+#[cfg(feature = "openssl")]
+pub type DefaultSuite = OpenSsl;
+#[cfg(all(feature = "rustcrypto", not(feature = "openssl")))]
+pub type DefaultSuite = RustCrypto;
+
+const KEY_LEN: usize = 32;
+const IV_LEN: usize = 16;
+
+/// The primitives the Kuda envelope is built from.
+///
+/// Payloads are encrypted with AES-256-CBC and PKCS#7 padding, under a key and
+/// IV derived from the envelope password with PBKDF2-HMAC-SHA1. The password
+/// itself is encrypted for the recipient with RSA and PKCS#1 v1.5 padding.
+pub trait CipherSuite {
+    type PrivateKey: Send + Sync + 'static;
+    type PublicKey: Send + Sync + 'static;
+
+    /// Parses a PEM encoded RSA private key.
+    fn private_key_from_pem(pem: &str) -> Result<Self::PrivateKey, CryptoError>;
+
+    /// Parses a PEM encoded RSA public key.
+    fn public_key_from_pem(pem: &str) -> Result<Self::PublicKey, CryptoError>;
+
+    /// Fills `out` with the PBKDF2-HMAC-SHA1 derivation of `password`.
+    fn pbkdf2_sha1(
+        password: &[u8],
+        salt: &[u8],
+        rounds: u32,
+        out: &mut [u8],
+    ) -> Result<(), CryptoError>;
+
+    fn encrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError>;
+
+    fn decrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError>;
+
+    fn rsa_encrypt(data: &[u8], key: &Self::PublicKey) -> Result<Vec<u8>, CryptoError>;
+
+    fn rsa_decrypt(data: &[u8], key: &Self::PrivateKey) -> Result<Vec<u8>, CryptoError>;
+}
+
+/// The AES key and IV derived from an envelope password.
+#[derive(Clone, Copy)]
+pub(crate) struct DerivedKey {
+    pub(crate) key: [u8; KEY_LEN],
+    pub(crate) iv: [u8; IV_LEN],
+}
+
+impl DerivedKey {
+    // 256-bit key and 128-bit iv
+    //  hashlib.pbkdf2_hmac('sha1', password, salt, 1000, dklen=32)
+    //  hashlib.pbkdf2_hmac('sha1', password, salt, 1000, dklen=16)
+    // PBKDF2 output is a prefix of any longer output for the same inputs, so
+    // one derivation yields both: the iv is the first 16 bytes of the key.
+    pub(crate) fn derive<S: CipherSuite>(password: &str) -> Result<DerivedKey, CryptoError> {
+        let mut key = [0; KEY_LEN];
+        S::pbkdf2_sha1(password.as_bytes(), b"randomsalt", 1000, &mut key)?;
+
+        let mut iv = [0; IV_LEN];
+        iv.copy_from_slice(&key[..IV_LEN]);
+        Ok(DerivedKey { key, iv })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/fixtures/keys/private.pem");
+    const PUBLIC_KEY: &str = include_str!("../../tests/fixtures/keys/public.pem");
+
+    fn pbkdf2<S: CipherSuite>(len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        S::pbkdf2_sha1(b"MOCK-ABC1234", b"randomsalt", 1000, &mut out).unwrap();
+        out
+    }
+
+    /// Checks `S` against the other suites and the AES and RSA round trips.
+    fn check_suite<S: CipherSuite>() {
+        let derived = DerivedKey::derive::<S>("MOCK-ABC1234").unwrap();
+        assert_eq!(derived.key.to_vec(), pbkdf2::<S>(KEY_LEN));
+        assert_eq!(derived.iv.to_vec(), pbkdf2::<S>(IV_LEN));
+        #[cfg(feature = "openssl")]
+        assert_eq!(derived.key.to_vec(), pbkdf2::<OpenSsl>(KEY_LEN));
+        #[cfg(feature = "rustcrypto")]
+        assert_eq!(derived.key.to_vec(), pbkdf2::<RustCrypto>(KEY_LEN));
+
+        let payload = br#"{"serviceType":"BANK_LIST"}"#;
+        let encrypted = S::encrypt_aes_256_cbc(payload, &derived.key, &derived.iv).unwrap();
+        assert_eq!(encrypted.len(), 32);
+        #[cfg(feature = "openssl")]
+        assert_eq!(
+            OpenSsl::decrypt_aes_256_cbc(&encrypted, &derived.key, &derived.iv).unwrap(),
+            payload
+        );
+        #[cfg(feature = "rustcrypto")]
+        assert_eq!(
+            RustCrypto::decrypt_aes_256_cbc(&encrypted, &derived.key, &derived.iv).unwrap(),
+            payload
+        );
+        assert!(S::decrypt_aes_256_cbc(&encrypted[..31], &derived.key, &derived.iv).is_err());
+
+        let public_key = S::public_key_from_pem(PUBLIC_KEY).unwrap();
+        let private_key = S::private_key_from_pem(PRIVATE_KEY).unwrap();
+        let encrypted = S::rsa_encrypt(b"MOCK-ABC1234", &public_key).unwrap();
+        assert_eq!(encrypted.len(), 256);
+        assert_eq!(
+            S::rsa_decrypt(&encrypted, &private_key).unwrap(),
+            b"MOCK-ABC1234"
+        );
+
+        assert!(S::private_key_from_pem("CHANGE_ME").is_err());
+        assert!(S::public_key_from_pem(PRIVATE_KEY).is_err());
+    }
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn test_openssl() {
+        check_suite::<OpenSsl>();
+    }
+
+    #[cfg(feature = "rustcrypto")]
+    #[test]
+    fn test_rustcrypto() {
+        check_suite::<RustCrypto>();
+    }
+}
@@ -0,0 +1,64 @@
+use openssl::hash::MessageDigest;
+use openssl::pkey::{Private, Public};
+use openssl::rsa::{Padding, Rsa};
+use openssl::symm::{self, Cipher};
+
+use super::{CipherSuite, IV_LEN, KEY_LEN};
+use crate::error::CryptoError;
+
+/// The cipher suite implemented with the system OpenSSL (the `openssl` feature).
+#[derive(Clone, Copy, Debug)]
+pub struct OpenSsl;
+
+impl CipherSuite for OpenSsl {
+    type PrivateKey = Rsa<Private>;
+    type PublicKey = Rsa<Public>;
+
+    fn private_key_from_pem(pem: &str) -> Result<Rsa<Private>, CryptoError> {
+        Ok(Rsa::private_key_from_pem(pem.as_bytes())?)
+    }
+
+    fn public_key_from_pem(pem: &str) -> Result<Rsa<Public>, CryptoError> {
+        Ok(Rsa::public_key_from_pem(pem.as_bytes())?)
+    }
+
+    fn pbkdf2_sha1(
+        password: &[u8],
+        salt: &[u8],
+        rounds: u32,
+        out: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        openssl::pkcs5::pbkdf2_hmac(password, salt, rounds as usize, MessageDigest::sha1(), out)?;
+        Ok(())
+    }
+
+    fn encrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError> {
+        symm::encrypt(Cipher::aes_256_cbc(), key, Some(iv), data).map_err(|_| CryptoError::Cipher)
+    }
+
+    fn decrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError> {
+        symm::decrypt(Cipher::aes_256_cbc(), key, Some(iv), data).map_err(|_| CryptoError::Cipher)
+    }
+
+    fn rsa_encrypt(data: &[u8], key: &Rsa<Public>) -> Result<Vec<u8>, CryptoError> {
+        let mut buf = vec![0; key.size() as usize];
+        let len = key.public_encrypt(data, &mut buf, Padding::PKCS1)?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    fn rsa_decrypt(data: &[u8], key: &Rsa<Private>) -> Result<Vec<u8>, CryptoError> {
+        let mut buf = vec![0; key.size() as usize];
+        let len = key.private_decrypt(data, &mut buf, Padding::PKCS1)?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
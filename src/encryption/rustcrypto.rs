@@ -0,0 +1,73 @@
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+
+use super::{CipherSuite, IV_LEN, KEY_LEN};
+use crate::error::CryptoError;
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+
+/// The cipher suite implemented in pure Rust with the RustCrypto crates (the
+/// `rustcrypto` feature).
+#[derive(Clone, Copy, Debug)]
+pub struct RustCrypto;
+
+impl CipherSuite for RustCrypto {
+    type PrivateKey = RsaPrivateKey;
+    type PublicKey = RsaPublicKey;
+
+    /// Accepts PKCS#1 (`RSA PRIVATE KEY`) and PKCS#8 (`PRIVATE KEY`) PEM.
+    fn private_key_from_pem(pem: &str) -> Result<RsaPrivateKey, CryptoError> {
+        RsaPrivateKey::from_pkcs1_pem(pem)
+            .map_err(rsa::Error::from)
+            .or_else(|_| RsaPrivateKey::from_pkcs8_pem(pem).map_err(rsa::Error::from))
+            .map_err(CryptoError::from)
+    }
+
+    /// Accepts SPKI (`PUBLIC KEY`) and PKCS#1 (`RSA PUBLIC KEY`) PEM.
+    fn public_key_from_pem(pem: &str) -> Result<RsaPublicKey, CryptoError> {
+        RsaPublicKey::from_public_key_pem(pem)
+            .map_err(|err| rsa::Error::from(rsa::pkcs8::Error::from(err)))
+            .or_else(|err| RsaPublicKey::from_pkcs1_pem(pem).map_err(|_| err))
+            .map_err(CryptoError::from)
+    }
+
+    fn pbkdf2_sha1(
+        password: &[u8],
+        salt: &[u8],
+        rounds: u32,
+        out: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, salt, rounds, out);
+        Ok(())
+    }
+
+    fn encrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError> {
+        Ok(Aes256CbcEnc::new(key.into(), iv.into()).encrypt_padded_vec_mut::<Pkcs7>(data))
+    }
+
+    fn decrypt_aes_256_cbc(
+        data: &[u8],
+        key: &[u8; KEY_LEN],
+        iv: &[u8; IV_LEN],
+    ) -> Result<Vec<u8>, CryptoError> {
+        Aes256CbcDec::new(key.into(), iv.into())
+            .decrypt_padded_vec_mut::<Pkcs7>(data)
+            .map_err(|_| CryptoError::Cipher)
+    }
+
+    fn rsa_encrypt(data: &[u8], key: &RsaPublicKey) -> Result<Vec<u8>, CryptoError> {
+        Ok(key.encrypt(&mut rand::thread_rng(), Pkcs1v15Encrypt, data)?)
+    }
+
+    fn rsa_decrypt(data: &[u8], key: &RsaPrivateKey) -> Result<Vec<u8>, CryptoError> {
+        Ok(key.decrypt(Pkcs1v15Encrypt, data)?)
+    }
+}
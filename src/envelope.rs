@@ -0,0 +1,196 @@
+//! The encrypted envelope of the Kuda v1 API.
+//!
+//! A request is serialized to JSON and encrypted with AES-256-CBC, under a key
+//! derived from a one-off password of the form `<client key>-<7 random
+//! characters>`. The ciphertext is posted as `{"data": "<base64>"}`, and the
+//! password, RSA encrypted with Kuda's public key, is sent in the `password`
+//! header. Kuda answers in kind with a [`KudaResponse`] sealed for your public
+//! key.
+//!
+//! `Client` seals and opens envelopes itself. These functions are for tooling
+//! that inspects captured traffic or builds requests to replay:
+//!
+//! ```ignore
+//! let response: Response<Value> = envelope::open(&captured, &private_key)?;
+//!
+//! let request = envelope::open_request::<Value>(&envelope, &kuda_private_key)?;
+//! let replay = envelope::seal(&request, &kuda_public_key, &client_key)?;
+//! ```
+//!
+//! The crypto comes from [`DefaultSuite`](crate::DefaultSuite).
+
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::encryption::{self, CipherSuite, DefaultSuite};
+use crate::error::Error;
+use crate::params::Request;
+
+/// A sealed request: the body to post and the value of its `password` header.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    pub body: String,
+    pub password: String,
+}
+
+/// A sealed response, as returned by Kuda.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KudaResponse {
+    pub data: String,
+    pub password: String,
+}
+
+/// The JSON body of a sealed request.
+#[derive(Serialize, Deserialize)]
+struct Body {
+    data: String,
+}
+
+/// Seals `request` for Kuda with a fresh password, as `Client` does.
+///
+/// `public_key` is Kuda's PEM encoded public key.
+#[allow(clippy::result_large_err)]
+pub fn seal<T: serde::Serialize>(
+    request: &Request<T>,
+    public_key: &str,
+    client_key: &str,
+) -> Result<Envelope, Error> {
+    seal_with_password(request, public_key, &encryption::password(client_key))
+}
+
+/// Seals `request` for Kuda with the given password.
+///
+/// The body only depends on the request and the password, so this reproduces
+/// the body of a captured request whose password is known.
+#[allow(clippy::result_large_err)]
+pub fn seal_with_password<T: serde::Serialize>(
+    request: &Request<T>,
+    public_key: &str,
+    password: &str,
+) -> Result<Envelope, Error> {
+    let public_key = DefaultSuite::public_key_from_pem(public_key).map_err(Error::InvalidKey)?;
+    let payload = serde_json::to_string(request).map_err(Error::serialize)?;
+    let (data, password) = encryption::encrypt::<DefaultSuite>(&public_key, password, &payload)?;
+
+    Ok(Envelope {
+        body: body(data)?,
+        password,
+    })
+}
+
+/// Opens a response Kuda sealed for `private_key`, your PEM encoded private key.
+#[allow(clippy::result_large_err)]
+pub fn open<T: DeserializeOwned>(response: &KudaResponse, private_key: &str) -> Result<T, Error> {
+    let private_key = DefaultSuite::private_key_from_pem(private_key).map_err(Error::InvalidKey)?;
+    let payload =
+        encryption::decrypt::<DefaultSuite>(&private_key, &response.password, &response.data)?;
+
+    serde_json::from_str(&payload).map_err(Error::deserialize)
+}
+
+/// Opens a sealed request with the private key matching the public key it
+/// was sealed for.
+#[allow(clippy::result_large_err)]
+pub fn open_request<T: DeserializeOwned>(
+    envelope: &Envelope,
+    private_key: &str,
+) -> Result<Request<T>, Error> {
+    let private_key = DefaultSuite::private_key_from_pem(private_key).map_err(Error::InvalidKey)?;
+    let payload = encryption::decrypt::<DefaultSuite>(
+        &private_key,
+        &envelope.password,
+        &data(envelope.body.as_bytes())?,
+    )?;
+
+    serde_json::from_str(&payload).map_err(Error::deserialize)
+}
+
+/// Wraps base64 encoded ciphertext in a request body.
+#[allow(clippy::result_large_err)]
+pub(crate) fn body(data: String) -> Result<String, Error> {
+    serde_json::to_string(&Body { data }).map_err(Error::serialize)
+}
+
+/// The base64 encoded ciphertext of a request body.
+#[allow(clippy::result_large_err)]
+pub(crate) fn data(body: &[u8]) -> Result<String, Error> {
+    let body: Body = serde_json::from_slice(body).map_err(Error::deserialize)?;
+    Ok(body.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::params::Response;
+    use crate::resources::GetBankListResponseData;
+
+    const PRIVATE_KEY: &str = include_str!("../tests/fixtures/keys/private.pem");
+    const PUBLIC_KEY: &str = include_str!("../tests/fixtures/keys/public.pem");
+    const REQUEST: &str = include_str!("../tests/fixtures/envelope/request.json");
+    const RESPONSE: &str = include_str!("../tests/fixtures/envelope/response.json");
+
+    /// The password both fixtures were sealed with.
+    const PASSWORD: &str = "MOCK-ABC1234";
+
+    fn request() -> Request<Value> {
+        Request {
+            service_type: "BANK_LIST".to_string(),
+            request_ref: "SP-C4T8J5N0N6QMJU0OK3FG".to_string(),
+            data: Some(Value::Object(Default::default())),
+        }
+    }
+
+    #[test]
+    fn test_seal_known_vector() {
+        let expected: Envelope = serde_json::from_str(REQUEST).unwrap();
+
+        let envelope = seal_with_password(&request(), PUBLIC_KEY, PASSWORD).unwrap();
+        assert_eq!(envelope.body, expected.body);
+        // RSA padding is random, so only the body is reproducible.
+        assert_ne!(envelope.password, expected.password);
+        assert_eq!(
+            open_request::<Value>(&envelope, PRIVATE_KEY).unwrap(),
+            request()
+        );
+    }
+
+    #[test]
+    fn test_open_known_vectors() {
+        let envelope: Envelope = serde_json::from_str(REQUEST).unwrap();
+        assert_eq!(
+            open_request::<Value>(&envelope, PRIVATE_KEY).unwrap(),
+            request()
+        );
+
+        let response: KudaResponse = serde_json::from_str(RESPONSE).unwrap();
+        let response: Response<GetBankListResponseData> = open(&response, PRIVATE_KEY).unwrap();
+        assert!(response.status);
+        assert_eq!(response.message, "Completed Successfully");
+        let banks = response.data.unwrap().banks;
+        assert_eq!(banks[0].bank_code, "999129");
+        assert_eq!(banks[0].bank_name, "Kuda.");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let envelope = seal(&request(), PUBLIC_KEY, "MOCK").unwrap();
+        assert_eq!(
+            open_request::<Value>(&envelope, PRIVATE_KEY).unwrap(),
+            request()
+        );
+
+        match open::<Value>(
+            &KudaResponse {
+                data: data(envelope.body.as_bytes()).unwrap(),
+                password: envelope.password,
+            },
+            PUBLIC_KEY,
+        ) {
+            Err(Error::InvalidKey(_)) => {}
+            other => panic!("expected Error::InvalidKey, got {:?}", other),
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use serde_derive::Deserialize;
 use serde_json::Value;
 
+use crate::params::ResponseCode;
+
+// `Error::Kuda` carries the whole `RequestError` unboxed so that it can be
+// built and matched directly, which makes `Error` larger than clippy's
+// `result_large_err` threshold. Functions returning it allow that lint
+// individually; the size is not a concern next to a network call.
+
 /// An error encountered when communicating with the Kuda API.
 #[derive(Debug)]
 pub enum Error {
@@ -14,6 +21,18 @@ pub enum Error {
     Serialize(Box<dyn std::error::Error + Send>),
     /// An error deserializing a response received from kuda.
     Deserialize(Box<dyn std::error::Error + Send>),
+    /// An error encrypting or decrypting a payload.
+    Crypto(CryptoError),
+    /// The RSA public or private key could not be parsed.
+    InvalidKey(CryptoError),
+    /// The encrypted envelope received from kuda is malformed.
+    Envelope(EnvelopeError),
+    /// A webhook notification failed validation.
+    InvalidNotification(&'static str),
+    /// The name enquiry returned a different beneficiary name than expected.
+    BeneficiaryMismatch(String),
+    /// The client configuration is incomplete or invalid.
+    InvalidConfig(&'static str),
     /// Indicates an operation not supported (yet?) by this library.
     Unsupported(&'static str),
     /// An invariant has been violated. Either a bug in this library or Kuda
@@ -21,7 +40,6 @@ pub enum Error {
 }
 
 impl Error {
-    #[allow(dead_code)]
     pub(crate) fn timeout() -> Error {
         Error::Http(HttpError::Timeout)
     }
@@ -51,6 +69,12 @@ impl std::fmt::Display for Error {
             Error::Io(ref err) => write!(f, ": {}", err),
             Error::Serialize(ref err) => write!(f, ": {}", err),
             Error::Deserialize(ref err) => write!(f, ": {}", err),
+            Error::Crypto(ref err) => write!(f, ": {}", err),
+            Error::InvalidKey(ref err) => write!(f, ": {}", err),
+            Error::Envelope(ref err) => write!(f, ": {}", err),
+            Error::InvalidNotification(msg) => write!(f, ": {}", msg),
+            Error::BeneficiaryMismatch(ref name) => write!(f, ": {}", name),
+            Error::InvalidConfig(msg) => write!(f, ": {}", msg),
             Error::Unsupported(msg) => write!(f, "{}", msg),
             Error::Unexpected(msg) => write!(f, "{}", msg),
         }
@@ -65,6 +89,12 @@ impl std::error::Error for Error {
             Error::Io(_) => "error reading response from kuda",
             Error::Serialize(_) => "error serializing a request",
             Error::Deserialize(_) => "error deserializing a response",
+            Error::Crypto(_) => "error encrypting or decrypting a payload",
+            Error::InvalidKey(_) => "invalid rsa key",
+            Error::Envelope(_) => "malformed response envelope",
+            Error::InvalidNotification(_) => "invalid webhook notification",
+            Error::BeneficiaryMismatch(_) => "beneficiary name does not match",
+            Error::InvalidConfig(_) => "invalid client configuration",
             Error::Unsupported(_) => "an unsupported operation was attempted",
             Error::Unexpected(_) => "an unexpected error has occurred",
         }
@@ -77,6 +107,12 @@ impl std::error::Error for Error {
             Error::Io(ref err) => Some(err),
             Error::Serialize(ref err) => Some(&**err),
             Error::Deserialize(ref err) => Some(&**err),
+            Error::Crypto(ref err) => Some(err),
+            Error::InvalidKey(ref err) => Some(err),
+            Error::Envelope(ref err) => Some(err),
+            Error::InvalidNotification(_) => None,
+            Error::BeneficiaryMismatch(_) => None,
+            Error::InvalidConfig(_) => None,
             Error::Unsupported(_) => None,
             Error::Unexpected(_) => None,
         }
@@ -91,20 +127,57 @@ impl From<RequestError> for Error {
 
 impl From<hyper::Error> for Error {
     fn from(err: hyper::Error) -> Error {
+        if is_timeout(&err) {
+            return Error::timeout();
+        }
         Error::Http(HttpError::Stream(err))
     }
 }
 
+/// Whether `err` was caused by a timeout, such as the connector's connect timeout.
+fn is_timeout(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<std::io::Error>() {
+            if err.kind() == std::io::ErrorKind::TimedOut {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
         Error::Io(err)
     }
 }
 
+impl From<http::Error> for Error {
+    fn from(err: http::Error) -> Error {
+        Error::Http(HttpError::Request(err))
+    }
+}
+
+impl From<CryptoError> for Error {
+    fn from(err: CryptoError) -> Error {
+        Error::Crypto(err)
+    }
+}
+
+impl From<EnvelopeError> for Error {
+    fn from(err: EnvelopeError) -> Error {
+        Error::Envelope(err)
+    }
+}
+
 #[derive(Debug)]
 pub enum HttpError {
     /// An error handling HTTP streams.
     Stream(hyper::Error),
+    /// An error building the HTTP request (e.g. an invalid header value).
+    Request(http::Error),
     /// The request timed out.
     Timeout,
 }
@@ -114,6 +187,7 @@ impl std::fmt::Display for HttpError {
         #[allow(deprecated)]
         match *self {
             HttpError::Stream(ref err) => err.fmt(f),
+            HttpError::Request(ref err) => err.fmt(f),
             HttpError::Timeout => f.write_str(std::error::Error::description(self)),
         }
     }
@@ -124,6 +198,7 @@ impl std::error::Error for HttpError {
         #[allow(deprecated)]
         match *self {
             HttpError::Stream(ref err) => err.description(),
+            HttpError::Request(_) => "error building request",
             HttpError::Timeout => "request timed out",
         }
     }
@@ -131,11 +206,119 @@ impl std::error::Error for HttpError {
     fn cause(&self) -> Option<&dyn std::error::Error> {
         match *self {
             HttpError::Stream(ref err) => Some(err),
+            HttpError::Request(ref err) => Some(err),
             HttpError::Timeout => None,
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum CryptoError {
+    /// An error reported by openssl while parsing keys, deriving keys or applying RSA.
+    #[cfg(feature = "openssl")]
+    OpenSsl(openssl::error::ErrorStack),
+    /// An error reported by the RustCrypto RSA implementation while parsing
+    /// keys or applying RSA. Shared, as `rsa::Error` cannot be cloned.
+    #[cfg(feature = "rustcrypto")]
+    Rsa(std::sync::Arc<rsa::Error>),
+    /// The AES ciphertext is truncated or its padding is invalid.
+    Cipher,
+}
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[allow(deprecated)]
+        match *self {
+            #[cfg(feature = "openssl")]
+            CryptoError::OpenSsl(ref err) => err.fmt(f),
+            #[cfg(feature = "rustcrypto")]
+            CryptoError::Rsa(ref err) => err.fmt(f),
+            CryptoError::Cipher => f.write_str(std::error::Error::description(self)),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {
+    fn description(&self) -> &str {
+        match *self {
+            #[cfg(feature = "openssl")]
+            CryptoError::OpenSsl(_) => "openssl error",
+            #[cfg(feature = "rustcrypto")]
+            CryptoError::Rsa(_) => "rsa error",
+            CryptoError::Cipher => "invalid cipher length or padding",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            #[cfg(feature = "openssl")]
+            CryptoError::OpenSsl(ref err) => Some(err),
+            #[cfg(feature = "rustcrypto")]
+            CryptoError::Rsa(ref err) => Some(&**err),
+            CryptoError::Cipher => None,
+        }
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl From<openssl::error::ErrorStack> for CryptoError {
+    fn from(err: openssl::error::ErrorStack) -> CryptoError {
+        CryptoError::OpenSsl(err)
+    }
+}
+
+#[cfg(feature = "rustcrypto")]
+impl From<rsa::Error> for CryptoError {
+    fn from(err: rsa::Error) -> CryptoError {
+        CryptoError::Rsa(std::sync::Arc::new(err))
+    }
+}
+
+#[derive(Debug)]
+pub enum EnvelopeError {
+    /// A field of the envelope is not valid base64.
+    Base64(base64::DecodeError),
+    /// The decrypted payload is not valid UTF-8.
+    Utf8(std::string::FromUtf8Error),
+}
+
+impl std::fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            EnvelopeError::Base64(ref err) => err.fmt(f),
+            EnvelopeError::Utf8(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {
+    fn description(&self) -> &str {
+        match *self {
+            EnvelopeError::Base64(_) => "invalid base64 in envelope",
+            EnvelopeError::Utf8(_) => "invalid utf-8 in envelope",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            EnvelopeError::Base64(ref err) => Some(err),
+            EnvelopeError::Utf8(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<base64::DecodeError> for EnvelopeError {
+    fn from(err: base64::DecodeError) -> EnvelopeError {
+        EnvelopeError::Base64(err)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for EnvelopeError {
+    fn from(err: std::string::FromUtf8Error) -> EnvelopeError {
+        EnvelopeError::Utf8(err)
+    }
+}
+
 /// An error reported by kuda in a request's response.
 ///
 /// For more details see https://developers.kuda.com/#block7Head
@@ -146,17 +329,53 @@ pub struct RequestError {
     pub http_status: u16,
 
     /// A human-readable message providing more details about the error.
-    #[serde(default)]
+    #[serde(default, alias = "Message")]
     pub message: String,
 
+    /// Kuda's code for the outcome, when the response carried one.
+    #[serde(default, rename = "ResponseCode", alias = "responseCode")]
+    pub response_code: Option<ResponseCode>,
+
+    /// The `requestRef` of the failed request, as echoed by Kuda.
+    #[serde(default, rename = "RequestReference", alias = "requestReference")]
+    pub request_reference: Option<String>,
+
+    /// Kuda's reference for the failed transaction, if one was created.
+    #[serde(
+        default,
+        rename = "TransactionReference",
+        alias = "transactionReference"
+    )]
+    pub transaction_reference: Option<String>,
+
     /// Some types of errors also include a details array:
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Value>,
 }
 
+impl RequestError {
+    /// An error for a response Kuda returned with `status: false`.
+    pub(crate) fn rejected<T>(response: &crate::params::Response<T>) -> RequestError {
+        RequestError {
+            http_status: 200,
+            message: response.message.clone(),
+            response_code: response.response_code.clone(),
+            request_reference: response.request_reference.clone(),
+            transaction_reference: response.transaction_reference.clone(),
+            data: None,
+        }
+    }
+}
+
 impl std::fmt::Display for RequestError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({})", self.http_status)?;
+        if let Some(ref code) = self.response_code {
+            write!(f, " [{}]", code)?;
+        }
+        if !self.message.is_empty() {
+            write!(f, " {}", self.message)?;
+        }
         if let Some(ref data) = self.data {
             write!(f, "{:?}", data)?;
         }
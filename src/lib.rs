@@ -1,25 +1,58 @@
 extern crate base64;
 extern crate hmac;
+#[cfg(feature = "openssl")]
 extern crate openssl;
 extern crate rand;
 
-pub use crate::error::{Error, RequestError};
-pub use crate::params::Headers;
+#[cfg(feature = "blocking")]
+pub use crate::client::blocking;
+pub use crate::client::builder::{ClientBuilder, Environment};
+pub use crate::client::retry::RetryPolicy;
+pub use crate::client::timeout::Timeouts;
+pub use crate::client::transport::KudaTransport;
+#[cfg(feature = "openssl")]
+pub use crate::encryption::OpenSsl;
+#[cfg(feature = "rustcrypto")]
+pub use crate::encryption::RustCrypto;
+pub use crate::encryption::{CipherSuite, DefaultSuite};
+// Only for benches/envelope.rs; not part of the public API.
+#[cfg(feature = "test-util")]
+#[doc(hidden)]
+pub use crate::encryption::Keys;
+pub use crate::error::{CryptoError, EnvelopeError, Error, HttpError, RequestError};
+pub use crate::money::Money;
+pub use crate::params::{Headers, ResponseCode};
 pub use crate::resources::*;
 
 pub use self::config::Client;
 pub use self::config::Response;
+pub use self::config::ResponseStream;
 
 mod client {
     pub mod r#async;
+    #[cfg(feature = "blocking")]
+    pub mod blocking;
+    pub mod builder;
+    mod connector;
+    pub mod retry;
+    pub mod timeout;
+    mod token;
+    pub mod transport;
 }
 
+pub mod datetime;
 mod encryption;
+pub mod envelope;
 mod error;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+pub mod money;
 pub mod params;
 mod resources;
+pub mod webhooks;
 
 mod config {
     pub type Client = crate::client::r#async::Client;
     pub type Response<T> = crate::client::r#async::Response<T>;
+    pub type ResponseStream<T> = crate::client::r#async::ResponseStream<T>;
 }
@@ -0,0 +1,995 @@
+//! A local stand-in for the Kuda API, for running integration tests offline.
+//!
+//! [`MockServer`] listens on a random local port and speaks the same RSA/AES
+//! envelope as Kuda (and the plain JSON + bearer token flavour of the v2 API).
+//! Requests are dispatched on their `serviceType` to an in-memory bank with a
+//! main account and any number of virtual accounts, so balances move as they
+//! would against the sandbox. Responses and errors can be scripted per service
+//! type for anything the built-in bank does not model.
+//!
+//! Enable the `test-util` feature to use it from outside this crate.
+//!
+//! ```ignore
+//! let server = MockServer::start().await;
+//! server.create_virtual_account("0012", Money::from_naira(500));
+//! server.fail("SINGLE_FUND_TRANSFER", 503, "Service unavailable");
+//!
+//! let client = server.client();
+//! let balance = VirtualAccount::get_virtual_account_balance(&client, params).await?;
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use http::{HeaderMap, Method, StatusCode};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request as HttpRequest, Response as HttpResponse};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+use crate::config::Client;
+use crate::encryption::Keys;
+use crate::envelope::{self, KudaResponse};
+use crate::error::Error;
+use crate::money::Money;
+use crate::params::{Request, Response, ResponseCode};
+use crate::resources::*;
+
+/// The bearer token handed out to clients created with [`MockServer::token_client`].
+const TOKEN: &str = "mock-token";
+
+/// The RSA key pair used for both ends of the envelope. It is not a secret.
+const PRIVATE_KEY: &str = include_str!("../tests/fixtures/keys/private.pem");
+const PUBLIC_KEY: &str = include_str!("../tests/fixtures/keys/public.pem");
+
+/// The client key embedded in envelope passwords.
+const CLIENT_KEY: &str = "MOCK";
+
+/// The account number of the main account.
+const MAIN_ACCOUNT_NUMBER: &str = "1100000000";
+
+/// The balance the main account starts with.
+const MAIN_ACCOUNT_BALANCE: Money = Money::from_naira(1_000_000);
+
+const CREDIT: i64 = 1;
+const DEBIT: i64 = 2;
+
+/// A local mock of the Kuda API.
+///
+/// The server shuts down when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+struct Shared {
+    keys: Keys,
+    state: Mutex<State>,
+}
+
+/// A response scripted with [`MockServer::respond`] or [`MockServer::fail`].
+enum Scripted {
+    Response(Value),
+    Error(StatusCode, String),
+}
+
+#[derive(Default)]
+struct State {
+    main_balance: Money,
+    main_postings: Vec<VirtualAccountTransactionHistoryPostingsHistory>,
+    accounts: Vec<Account>,
+    transfers: HashMap<String, TransferStatusResponseData>,
+    scripted: HashMap<String, VecDeque<Scripted>>,
+    delays: HashMap<String, VecDeque<Duration>>,
+    requests: Vec<Request<Value>>,
+    headers: Vec<HeaderMap>,
+    token_requests: usize,
+    in_flight: usize,
+    max_in_flight: usize,
+    sequence: u64,
+}
+
+struct Account {
+    details: VirtualAccountGetResponseAccount,
+    balance: Money,
+    enabled: bool,
+    postings: Vec<VirtualAccountTransactionHistoryPostingsHistory>,
+}
+
+impl MockServer {
+    /// Starts a server on a random local port.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub async fn start() -> MockServer {
+        let shared = Arc::new(Shared {
+            keys: Keys::new(PRIVATE_KEY, PUBLIC_KEY, CLIENT_KEY.to_string()),
+            state: Mutex::new(State {
+                main_balance: MAIN_ACCOUNT_BALANCE,
+                ..Default::default()
+            }),
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind mock server");
+        listener
+            .set_nonblocking(true)
+            .expect("failed to configure mock server");
+        let addr = listener.local_addr().expect("mock server has no address");
+
+        let service = shared.clone();
+        let make_service = make_service_fn(move |_| {
+            let shared = service.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(shared.clone(), req))) }
+        });
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let server = hyper::Server::from_tcp(listener)
+            .expect("failed to start mock server")
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                signal.await.ok();
+            });
+        tokio::spawn(server);
+
+        MockServer {
+            addr,
+            shared,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// The `scheme://host` the server listens on.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A client that talks to this server with the RSA/AES envelope.
+    pub fn client(&self) -> Client {
+        Client::from_url(self.url(), PRIVATE_KEY, PUBLIC_KEY, CLIENT_KEY)
+    }
+
+    /// A client that talks to this server with bearer token authentication.
+    pub fn token_client(&self) -> Client {
+        Client::with_api_key(self.url(), "mock@example.com", "mock-api-key")
+    }
+
+    /// The RSA private key, in PEM format, shared by the server and its clients.
+    pub fn private_key(&self) -> &str {
+        PRIVATE_KEY
+    }
+
+    /// The RSA public key, in PEM format, shared by the server and its clients.
+    pub fn public_key(&self) -> &str {
+        PUBLIC_KEY
+    }
+
+    /// Opens a virtual account with an initial balance, returning its account number.
+    pub fn create_virtual_account(&self, tracking_reference: &str, balance: Money) -> String {
+        let mut state = self.state();
+        let account_number = state.open_account(VirtualAccountCreateRequest {
+            tracking_reference: tracking_reference.to_string(),
+            first_name: "Mock".to_string(),
+            last_name: "Customer".to_string(),
+            email: "customer@example.com".to_string(),
+            phone_number: "08000000000".to_string(),
+        });
+        state.account(tracking_reference).unwrap().balance = balance;
+        account_number
+    }
+
+    /// The balance of a virtual account, if it exists.
+    pub fn balance(&self, tracking_reference: &str) -> Option<Money> {
+        self.state()
+            .account(tracking_reference)
+            .map(|account| account.balance)
+    }
+
+    /// The balance of the main account.
+    pub fn main_balance(&self) -> Money {
+        self.state().main_balance
+    }
+
+    /// Sets the balance of the main account.
+    pub fn set_main_balance(&self, balance: Money) {
+        self.state().main_balance = balance;
+    }
+
+    /// Answers the next call to `service_type` with `response` instead of the
+    /// built-in behaviour.
+    ///
+    /// Scripted responses are used once each, in the order they were added.
+    pub fn respond<T: Serialize>(&self, service_type: &str, response: Response<T>) {
+        let response = serde_json::to_value(response).expect("failed to serialize response");
+        self.script(service_type, Scripted::Response(response));
+    }
+
+    /// Answers the next call to `service_type` with a successful response
+    /// carrying `data`.
+    pub fn succeed<T: Serialize>(&self, service_type: &str, data: T) {
+        self.respond(service_type, success("Request successful.", data));
+    }
+
+    /// Fails the next call to `service_type` with an HTTP error.
+    pub fn fail(&self, service_type: &str, http_status: u16, message: &str) {
+        let status = StatusCode::from_u16(http_status).expect("invalid http status");
+        self.script(service_type, Scripted::Error(status, message.to_string()));
+    }
+
+    /// Holds back the answer to the next call to `service_type` for `delay`.
+    ///
+    /// The call is still processed straight away, as if only the response
+    /// were slow to arrive.
+    pub fn delay(&self, service_type: &str, delay: Duration) {
+        self.state()
+            .delays
+            .entry(service_type.to_string())
+            .or_default()
+            .push_back(delay);
+    }
+
+    /// Every service call received so far, decrypted, in order.
+    pub fn requests(&self) -> Vec<Request<Value>> {
+        self.state().requests.clone()
+    }
+
+    /// The HTTP headers of every request received so far, token requests
+    /// included, in order.
+    pub fn request_headers(&self) -> Vec<HeaderMap> {
+        self.state().headers.clone()
+    }
+
+    /// The number of bearer tokens issued so far.
+    pub fn token_requests(&self) -> usize {
+        self.state().token_requests
+    }
+
+    /// The most service calls that have been in flight at once, from receipt
+    /// of the request until its answer was sent.
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.state().max_in_flight
+    }
+
+    fn script(&self, service_type: &str, scripted: Scripted) {
+        self.state()
+            .scripted
+            .entry(service_type.to_string())
+            .or_default()
+            .push_back(scripted);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.shared.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+async fn handle(
+    shared: Arc<Shared>,
+    req: HttpRequest<Body>,
+) -> Result<HttpResponse<Body>, Infallible> {
+    if req.method() != Method::POST {
+        return Ok(error(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed"));
+    }
+
+    let path = req.uri().path().to_string();
+    shared
+        .state
+        .lock()
+        .unwrap()
+        .headers
+        .push(req.headers().clone());
+    let password = header(&req, "password");
+    let authorization = header(&req, "authorization");
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Err(_) => return Ok(error(StatusCode::BAD_REQUEST, "Unreadable body")),
+        Ok(body) => body,
+    };
+
+    if path.ends_with("/Account/GetToken") {
+        shared.state.lock().unwrap().token_requests += 1;
+        return Ok(json_response(&TOKEN));
+    }
+    let _in_flight = InFlight::new(&shared);
+
+    if let Some(password) = password {
+        let request = match shared.open(&password, &body) {
+            Err(_) => return Ok(error(StatusCode::BAD_REQUEST, "Invalid envelope")),
+            Ok(request) => request,
+        };
+        let (delay, scripted) = shared.dispatch(request);
+        pause(delay).await;
+        return Ok(match scripted {
+            Scripted::Error(status, message) => error(status, &message),
+            Scripted::Response(response) => match shared.seal(&response) {
+                Err(_) => error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to seal response"),
+                Ok(envelope) => json_response(&envelope),
+            },
+        });
+    }
+
+    if authorization.as_deref() != Some(&format!("Bearer {}", TOKEN)) {
+        return Ok(error(StatusCode::UNAUTHORIZED, "Unauthorized"));
+    }
+    let request = match serde_json::from_slice(&body) {
+        Err(_) => return Ok(error(StatusCode::BAD_REQUEST, "Invalid request")),
+        Ok(request) => request,
+    };
+    let (delay, scripted) = shared.dispatch(request);
+    pause(delay).await;
+    Ok(match scripted {
+        Scripted::Error(status, message) => error(status, &message),
+        Scripted::Response(response) => json_response(&response),
+    })
+}
+
+/// Counts a service call as in flight until dropped.
+struct InFlight(Arc<Shared>);
+
+impl InFlight {
+    fn new(shared: &Arc<Shared>) -> InFlight {
+        let mut state = shared.state.lock().unwrap();
+        state.in_flight += 1;
+        state.max_in_flight = state.max_in_flight.max(state.in_flight);
+        InFlight(shared.clone())
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().in_flight -= 1;
+    }
+}
+
+impl Shared {
+    /// Decrypts an enveloped request body.
+    #[allow(clippy::result_large_err)]
+    fn open(&self, password: &str, body: &[u8]) -> Result<Request<Value>, Error> {
+        let data = self.keys.decrypt(password, &envelope::data(body)?)?;
+
+        serde_json::from_str(&data).map_err(Error::deserialize)
+    }
+
+    /// Encrypts a response body into an envelope.
+    #[allow(clippy::result_large_err)]
+    fn seal(&self, response: &Value) -> Result<KudaResponse, Error> {
+        let password = format!("{}-RESPONSE", CLIENT_KEY);
+        let payload = serde_json::to_string(response).map_err(Error::serialize)?;
+
+        let (data, password) = self.keys.encrypt(&password, &payload)?;
+
+        Ok(KudaResponse { data, password })
+    }
+
+    /// Records and answers a service call, returning how long to wait before
+    /// sending the answer.
+    fn dispatch(&self, request: Request<Value>) -> (Option<Duration>, Scripted) {
+        let mut state = self.state.lock().unwrap();
+        state.requests.push(request.clone());
+
+        let delay = state
+            .delays
+            .get_mut(&request.service_type)
+            .and_then(VecDeque::pop_front);
+        let scripted = state
+            .scripted
+            .get_mut(&request.service_type)
+            .and_then(VecDeque::pop_front);
+        if let Some(scripted) = scripted {
+            return (delay, scripted);
+        }
+
+        let mut response = state.call(&request);
+        response.request_reference = Some(request.request_ref);
+        let response = serde_json::to_value(response).expect("failed to serialize response");
+        (delay, Scripted::Response(response))
+    }
+}
+
+impl State {
+    /// Performs a service call against the in-memory bank.
+    fn call(&mut self, request: &Request<Value>) -> Response<Value> {
+        let data = request.data.clone().unwrap_or(Value::Null);
+        let result = match request.service_type.as_str() {
+            "BANK_LIST" => Ok(Response {
+                status: false,
+                message: "Completed Successfully".to_string(),
+                data: Some(json!({
+                    "banks": [
+                        { "bankCode": "999129", "bankName": "Kuda." },
+                        { "bankCode": "000013", "bankName": "GTBank" },
+                        { "bankCode": "000014", "bankName": "Access Bank" },
+                    ]
+                })),
+                ..Default::default()
+            }),
+            "ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE" => Ok(balance(self.main_balance)),
+            "ADMIN_MAIN_ACCOUNT_TRANSACTIONS" => {
+                parse(data).map(|req: AdminAccountTransactionHistoryRequest| {
+                    history(&self.main_postings, req.page_number, req.page_size, None)
+                })
+            }
+            "ADMIN_MAIN_ACCOUNT_FILTERED_TRANSACTIONS" => {
+                parse(data).map(|req: AdminAccountFilteredTransactionHistoryRequest| {
+                    let range = (req.start_date, req.end_date);
+                    history(
+                        &self.main_postings,
+                        req.page_number,
+                        req.page_size,
+                        Some(range),
+                    )
+                })
+            }
+            "NAME_ENQUIRY" => parse(data).map(|req| self.enquire(req)),
+            "SINGLE_FUND_TRANSFER" => {
+                parse(data).and_then(|req| self.transfer(&request.request_ref, req))
+            }
+            "TRANSACTION_STATUS_QUERY" => parse(data).map(|req| self.transfer_status(req)),
+            "ADMIN_CREATE_VIRTUAL_ACCOUNT" => parse(data).and_then(|req| self.create(req)),
+            "ADMIN_RETRIEVE_SINGLE_VIRTUAL_ACCOUNT" => parse(data)
+                .and_then(|req: VirtualAccountGetRequest| self.find(&req.tracking_reference))
+                .map(|account| {
+                    success(
+                        "Request successful.",
+                        VirtualAccountGetResponseData {
+                            account: account.details.clone(),
+                        },
+                    )
+                }),
+            "RETRIEVE_VIRTUAL_ACCOUNT_BALANCE" => parse(data)
+                .and_then(|req: VirtualAccountGetRequest| self.find(&req.tracking_reference))
+                .map(|account| balance(account.balance)),
+            "ADMIN_VIRTUAL_ACCOUNT_TRANSACTIONS" => {
+                parse(data).and_then(|req: VirtualAccountTransactionHistoryRequest| {
+                    let account = self.find(&req.tracking_reference)?;
+                    Ok(history(
+                        &account.postings,
+                        req.page_number,
+                        req.page_size,
+                        None,
+                    ))
+                })
+            }
+            "ADMIN_VIRTUAL_ACCOUNT_FILTERED_TRANSACTIONS" => {
+                parse(data).and_then(|req: VirtualAccountFilteredTransactionHistoryRequest| {
+                    let account = self.find(&req.tracking_reference)?;
+                    let range = (req.start_date, req.end_date);
+                    Ok(history(
+                        &account.postings,
+                        req.page_number,
+                        req.page_size,
+                        Some(range),
+                    ))
+                })
+            }
+            "FUND_VIRTUAL_ACCOUNT" => parse(data).and_then(|req| self.fund(req, true)),
+            "WITHDRAW_VIRTUAL_ACCOUNT" => parse(data).and_then(|req| self.fund(req, false)),
+            "VIRTUAL_ACCOUNT_FUND_TRANSFER" => {
+                parse(data).and_then(|req| self.transfer_virtual(&request.request_ref, req))
+            }
+            "UPDATE_VIRTUAL_ACCOUNT" => parse(data).and_then(|req: VirtualAccountUpdateRequest| {
+                let account = self.account_mut(&req.tracking_reference)?;
+                let details = &mut account.details;
+                details.first_name = req.first_name;
+                details.last_name = req.last_name;
+                details.email = req.email;
+                details.account_name = format!("{} {}", details.first_name, details.last_name);
+                Ok(success("Request successful.", Value::Null))
+            }),
+            "ADMIN_ENABLE_VIRTUAL_ACCOUNT" => parse(data).and_then(|req| self.enable(req, true)),
+            "ADMIN_DISABLE_VIRTUAL_ACCOUNT" => parse(data).and_then(|req| self.enable(req, false)),
+            "ADMIN_VIRTUAL_ACCOUNTS" => parse(data).map(|req: VirtualAccountListRequest| {
+                let accounts: Vec<_> = self
+                    .accounts
+                    .iter()
+                    .map(|account| account.details.clone())
+                    .collect();
+                success(
+                    "Request successful.",
+                    VirtualAccountListResponseData {
+                        total_count: accounts.len() as i64,
+                        accounts: page(&accounts, req.page_number, req.page_size),
+                    },
+                )
+            }),
+            _ => Err(rejected(ResponseCode::Failed, "Invalid service type")),
+        };
+
+        result.unwrap_or_else(Rejection::into_response)
+    }
+
+    fn enquire(&mut self, req: AccountEnquiryRequest) -> Response<Value> {
+        let beneficiary_name = self
+            .accounts
+            .iter()
+            .find(|account| account.details.account_number == req.beneficiary_account_number)
+            .map(|account| account.details.account_name.clone())
+            .unwrap_or_else(|| "Mock Beneficiary".to_string());
+
+        let sequence = self.next();
+        success(
+            "Request successful.",
+            AccountEnquiryResponseData {
+                beneficiary_account_number: req.beneficiary_account_number,
+                beneficiary_name,
+                sender_account_number: MAIN_ACCOUNT_NUMBER.to_string(),
+                sender_name: None,
+                beneficiary_customer_id: 0,
+                beneficiary_bank_code: req.beneficiary_bank_code,
+                name_enquiry_id: sequence as i64,
+                response_code: "00".to_string(),
+                transfer_charge: Money::ZERO,
+                session_id: format!("{:030}", sequence),
+            },
+        )
+    }
+
+    fn transfer(
+        &mut self,
+        request_ref: &str,
+        req: AdminAccountFundTransferRequest,
+    ) -> Result<Response<Value>, Rejection> {
+        if req.amount > self.main_balance {
+            return Err(rejected(
+                ResponseCode::InsufficientFunds,
+                "Insufficient funds",
+            ));
+        }
+
+        let posting = self.posting(
+            MAIN_ACCOUNT_NUMBER,
+            self.main_balance,
+            -req.amount,
+            &req.narration,
+        );
+        self.main_balance = posting.balance_after;
+        self.main_postings.push(posting);
+        Ok(self.transferred(request_ref, req.amount))
+    }
+
+    fn transfer_virtual(
+        &mut self,
+        request_ref: &str,
+        req: VirtualAccountFundTransferRequest,
+    ) -> Result<Response<Value>, Rejection> {
+        let sequence = self.next();
+        let account = self.account_mut(&req.tracking_reference)?;
+        if !account.enabled {
+            return Err(rejected(ResponseCode::Failed, "Account is disabled"));
+        }
+        if req.amount > account.balance {
+            return Err(rejected(
+                ResponseCode::InsufficientFunds,
+                "Insufficient funds",
+            ));
+        }
+
+        let posting = posting(sequence, account, -req.amount, &req.narration);
+        account.balance = posting.balance_after;
+        account.postings.push(posting);
+        Ok(self.transferred(request_ref, req.amount))
+    }
+
+    fn transferred(&mut self, request_ref: &str, amount: Money) -> Response<Value> {
+        let sequence = self.next();
+        let transaction_reference = format!("{:027}", sequence);
+        let session_id = format!("{:030}", sequence);
+        let transaction_date = now();
+        self.transfers.insert(
+            request_ref.to_string(),
+            TransferStatusResponseData {
+                request_reference: Some(request_ref.to_string()),
+                transaction_reference: Some(transaction_reference.clone()),
+                session_id: Some(session_id.clone()),
+                status: Some(TransferStatus::Successful),
+                amount: Some(amount),
+                charge: Some(Money::ZERO),
+                transaction_date: Some(transaction_date),
+                completion_date: Some(transaction_date),
+            },
+        );
+        Response {
+            transaction_reference: Some(transaction_reference.clone()),
+            response_code: Some(ResponseCode::Success),
+            ..success(
+                "Transaction successful.",
+                FundTransferResponseData {
+                    transaction_reference: Some(transaction_reference),
+                    session_id: Some(session_id),
+                    status: Some(TransferStatus::Successful),
+                    charge: Some(Money::ZERO),
+                    transaction_date: Some(transaction_date),
+                },
+            )
+        }
+    }
+
+    fn transfer_status(&mut self, req: AdminAccountCheckTransferRequest) -> Response<Value> {
+        match self.transfers.get(&req.transaction_request_reference) {
+            Some(transfer) => Response {
+                transaction_reference: transfer.transaction_reference.clone(),
+                response_code: Some(ResponseCode::Success),
+                ..success("Transaction successful", transfer.clone())
+            },
+            None => success("RecordNotFound", Value::Null),
+        }
+    }
+
+    fn create(&mut self, req: VirtualAccountCreateRequest) -> Result<Response<Value>, Rejection> {
+        if self.account(&req.tracking_reference).is_some() {
+            return Err(rejected(
+                ResponseCode::Failed,
+                "Tracking reference already exists",
+            ));
+        }
+
+        let account_number = self.open_account(req);
+        Ok(success(
+            "Completed Successfully",
+            VirtualAccountCreateResponseData { account_number },
+        ))
+    }
+
+    fn fund(
+        &mut self,
+        req: VirtualAccountFundWithdrawRequest,
+        fund: bool,
+    ) -> Result<Response<Value>, Rejection> {
+        let main_balance = self.main_balance;
+        let sequence = self.next();
+        let account = self.account_mut(&req.tracking_reference)?;
+        let (available, amount) = if fund {
+            (main_balance, req.amount)
+        } else {
+            (account.balance, -req.amount)
+        };
+        if req.amount > available {
+            return Err(rejected(
+                ResponseCode::InsufficientFunds,
+                "Insufficient funds",
+            ));
+        }
+
+        let posting = posting(sequence, account, amount, &req.narration);
+        account.balance = posting.balance_after;
+        account.postings.push(posting);
+        self.main_balance -= amount;
+        Ok(success("Transaction successful", Value::Null))
+    }
+
+    fn enable(
+        &mut self,
+        req: VirtualAccountGetRequest,
+        enabled: bool,
+    ) -> Result<Response<Value>, Rejection> {
+        let account = self.account_mut(&req.tracking_reference)?;
+        account.enabled = enabled;
+        Ok(success(
+            "Request successful.",
+            VirtualAccountStatusResponseData {
+                account_number: account.details.account_number.clone(),
+            },
+        ))
+    }
+
+    fn open_account(&mut self, req: VirtualAccountCreateRequest) -> String {
+        let account_number = format!("2{:09}", self.next());
+        self.accounts.push(Account {
+            details: VirtualAccountGetResponseAccount {
+                account_number: account_number.clone(),
+                account_name: format!("{} {}", req.first_name, req.last_name),
+                email: req.email,
+                phone_number: req.phone_number,
+                last_name: req.last_name,
+                first_name: req.first_name,
+                tracking_reference: req.tracking_reference,
+                creation_date: now(),
+            },
+            balance: Money::ZERO,
+            enabled: true,
+            postings: Vec::new(),
+        });
+        account_number
+    }
+
+    fn account(&mut self, tracking_reference: &str) -> Option<&mut Account> {
+        self.accounts
+            .iter_mut()
+            .find(|account| account.details.tracking_reference == tracking_reference)
+    }
+
+    fn account_mut(&mut self, tracking_reference: &str) -> Result<&mut Account, Rejection> {
+        self.account(tracking_reference)
+            .ok_or_else(|| rejected(ResponseCode::Failed, "Virtual account not found"))
+    }
+
+    fn find(&mut self, tracking_reference: &str) -> Result<&Account, Rejection> {
+        self.account_mut(tracking_reference)
+            .map(|account| &*account)
+    }
+
+    fn posting(
+        &mut self,
+        account_number: &str,
+        opening_balance: Money,
+        amount: Money,
+        narration: &str,
+    ) -> VirtualAccountTransactionHistoryPostingsHistory {
+        let sequence = self.next();
+        new_posting(sequence, account_number, opening_balance, amount, narration)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+}
+
+/// Records a movement of `amount` (negative for debits) on a virtual account.
+fn posting(
+    sequence: u64,
+    account: &Account,
+    amount: Money,
+    narration: &str,
+) -> VirtualAccountTransactionHistoryPostingsHistory {
+    let account_number = &account.details.account_number;
+    new_posting(sequence, account_number, account.balance, amount, narration)
+}
+
+fn new_posting(
+    sequence: u64,
+    account_number: &str,
+    opening_balance: Money,
+    amount: Money,
+    narration: &str,
+) -> VirtualAccountTransactionHistoryPostingsHistory {
+    VirtualAccountTransactionHistoryPostingsHistory {
+        reference_number: format!("{:027}", sequence),
+        reversal_reference_number: Value::Null,
+        account_number: account_number.to_string(),
+        linked_account_number: Value::Null,
+        real_date: now(),
+        amount: if amount.is_negative() {
+            -amount
+        } else {
+            amount
+        },
+        opening_balance,
+        balance_after: opening_balance + amount,
+        narration: narration.to_string(),
+        instrument_number: String::new(),
+        posting_record_type: if amount.is_negative() { DEBIT } else { CREDIT },
+        posted_by: "mock".to_string(),
+    }
+}
+
+fn history(
+    postings: &[VirtualAccountTransactionHistoryPostingsHistory],
+    page_number: i64,
+    page_size: i64,
+    range: Option<(NaiveDateTime, NaiveDateTime)>,
+) -> Response<Value> {
+    let postings: Vec<_> = postings
+        .iter()
+        .filter(|posting| match range {
+            Some((start, end)) => start <= posting.real_date && posting.real_date <= end,
+            None => true,
+        })
+        .cloned()
+        .collect();
+    let total = |record_type| {
+        postings
+            .iter()
+            .filter(|posting| posting.posting_record_type == record_type)
+            .map(|posting| posting.amount)
+            .sum::<Money>()
+    };
+
+    success(
+        "Request successful.",
+        VirtualAccountTransactionHistoryResponseData {
+            total_record_in_store: postings.len() as i64,
+            total_debit: total(DEBIT),
+            total_credit: total(CREDIT),
+            postings_history: page(&postings, page_number, page_size),
+            message: Value::Null,
+            status_code: "OK".to_string(),
+        },
+    )
+}
+
+fn page<T: Clone>(records: &[T], page_number: i64, page_size: i64) -> Vec<T> {
+    let page_size = page_size.max(1) as usize;
+    let skip = (page_number.max(1) as usize - 1) * page_size;
+    records.iter().skip(skip).take(page_size).cloned().collect()
+}
+
+fn balance(balance: Money) -> Response<Value> {
+    success(
+        "Operation successful",
+        VirtualAccountGetBalanceResponseData {
+            ledger_balance: balance,
+            available_balance: balance,
+            withdrawable_balance: balance,
+        },
+    )
+}
+
+fn parse<T: DeserializeOwned>(data: Value) -> Result<T, Rejection> {
+    serde_json::from_value(data).map_err(|_| rejected(ResponseCode::Failed, "Invalid request data"))
+}
+
+fn success<T: Serialize>(message: &str, data: T) -> Response<Value> {
+    let data = serde_json::to_value(data).expect("failed to serialize response data");
+    Response {
+        status: true,
+        message: message.to_string(),
+        data: if data.is_null() { None } else { Some(data) },
+        ..Default::default()
+    }
+}
+
+/// A service call the in-memory bank refused, as a response code and message.
+struct Rejection(ResponseCode, &'static str);
+
+impl Rejection {
+    fn into_response(self) -> Response<Value> {
+        Response {
+            response_code: Some(self.0),
+            status: false,
+            message: self.1.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+fn rejected(response_code: ResponseCode, message: &'static str) -> Rejection {
+    Rejection(response_code, message)
+}
+
+fn now() -> NaiveDateTime {
+    chrono::Utc::now().naive_utc()
+}
+
+async fn pause(delay: Option<Duration>) {
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn header(req: &HttpRequest<Body>, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn json_response<T: Serialize>(body: &T) -> HttpResponse<Body> {
+    let body = serde_json::to_vec(body).expect("failed to serialize response body");
+    let mut response = HttpResponse::new(Body::from(body));
+    response.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+fn error(status: StatusCode, message: &str) -> HttpResponse<Body> {
+    let mut response = json_response(&json!({ "message": message }));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Response as R;
+
+    fn balance_request(tracking_reference: &str) -> VirtualAccountGetRequest {
+        let mut params = VirtualAccountGetRequest::new();
+        params.tracking_reference = tracking_reference.to_string();
+        params
+    }
+
+    #[tokio::test]
+    async fn test_mock_moves_balances() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(50));
+
+        let mut params = VirtualAccountFundWithdrawRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.amount = Money::from_naira(25);
+        params.narration = "top up".to_string();
+        let funded = VirtualAccount::fund_virtual_account(&client, params)
+            .await
+            .unwrap();
+        assert!(funded.status);
+
+        let balance = VirtualAccount::get_virtual_account_balance(&client, balance_request("0012"))
+            .await
+            .unwrap();
+        assert_eq!(
+            balance.data.unwrap().available_balance,
+            Money::from_naira(75)
+        );
+        assert_eq!(server.balance("0012"), Some(Money::from_naira(75)));
+        assert_eq!(
+            server.main_balance(),
+            MAIN_ACCOUNT_BALANCE - Money::from_naira(25)
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].service_type, "FUND_VIRTUAL_ACCOUNT");
+        assert_eq!(requests[0].data.as_ref().unwrap()["amount"], json!("2500"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_scripted_responses() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.respond(
+            "RETRIEVE_VIRTUAL_ACCOUNT_BALANCE",
+            R::<Value> {
+                status: false,
+                message: "Service is busy".to_string(),
+                ..Default::default()
+            },
+        );
+        server.fail(
+            "RETRIEVE_VIRTUAL_ACCOUNT_BALANCE",
+            503,
+            "Service unavailable",
+        );
+
+        let response =
+            VirtualAccount::get_virtual_account_balance(&client, balance_request("0012"))
+                .await
+                .unwrap();
+        assert!(!response.status);
+        assert_eq!(response.message, "Service is busy");
+
+        match VirtualAccount::get_virtual_account_balance(&client, balance_request("0012")).await {
+            Err(Error::Kuda(err)) => {
+                assert_eq!(err.http_status, 503);
+                assert_eq!(err.message, "Service unavailable");
+            }
+            other => panic!("{:?}", other),
+        }
+
+        let response =
+            VirtualAccount::get_virtual_account_balance(&client, balance_request("0012"))
+                .await
+                .unwrap();
+        assert_eq!(response.message, "Virtual account not found");
+    }
+
+    #[tokio::test]
+    async fn test_mock_token_auth() {
+        let server = MockServer::start().await;
+        let client = server.token_client();
+        server.fail("ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE", 401, "Unauthorized");
+
+        let response =
+            AdminAccount::get_admin_account_balance(&client, AdminAccountBalanceRequest::new())
+                .await
+                .unwrap();
+        assert!(response.status);
+        assert_eq!(
+            response.data.unwrap().available_balance,
+            MAIN_ACCOUNT_BALANCE
+        );
+        assert_eq!(server.token_requests(), 2);
+    }
+}
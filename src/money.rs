@@ -0,0 +1,383 @@
+//! An exact amount of naira, stored as an integer number of kobo.
+//!
+//! Kuda sends amounts in kobo, as strings in requests and as JSON numbers in
+//! responses. `Money` serializes as a string of kobo and deserializes from
+//! either form, rejecting fractions of a kobo; use [`number`] or [`option`]
+//! for fields Kuda sends as numbers so they round-trip unchanged.
+//!
+//! The arithmetic operators behave like those of `i64`: they panic on
+//! overflow in debug builds and wrap in release builds. Use the `checked_*`
+//! methods where an amount may be out of range.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+
+const KOBO_PER_NAIRA: i64 = 100;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_kobo(kobo: i64) -> Money {
+        Money(kobo)
+    }
+
+    pub const fn from_naira(naira: i64) -> Money {
+        Money(naira * KOBO_PER_NAIRA)
+    }
+
+    /// The amount in kobo.
+    pub const fn kobo(self) -> i64 {
+        self.0
+    }
+
+    /// The whole naira part of the amount, truncated towards zero.
+    pub const fn naira(self) -> i64 {
+        self.0 / KOBO_PER_NAIRA
+    }
+
+    /// The kobo part of the amount, between -99 and 99.
+    pub const fn kobo_part(self) -> i64 {
+        self.0 % KOBO_PER_NAIRA
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    pub fn checked_mul(self, quantity: i64) -> Option<Money> {
+        self.0.checked_mul(quantity).map(Money)
+    }
+
+    pub fn checked_neg(self) -> Option<Money> {
+        self.0.checked_neg().map(Money)
+    }
+
+    /// Parses an amount in naira, e.g. `"1234.56"`, `"₦1,234.5"` or `"-20"`.
+    ///
+    /// Amounts in kobo, as Kuda sends them, are read by deserializing instead.
+    pub fn from_naira_str(s: &str) -> Result<Money, ParseMoneyError> {
+        let s = s.trim();
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let s = s.trim_start_matches('₦').replace(',', "");
+        let (naira, kobo) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (&s[..], ""),
+        };
+
+        if naira.is_empty() || !naira.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseMoneyError("invalid naira amount"));
+        }
+        if kobo.len() > 2 || !kobo.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseMoneyError("invalid kobo amount"));
+        }
+
+        let naira: i64 = naira
+            .parse()
+            .map_err(|_| ParseMoneyError("amount out of range"))?;
+        let kobo: i64 = match kobo.len() {
+            0 => 0,
+            1 => kobo.parse::<i64>().unwrap_or(0) * 10,
+            _ => kobo.parse().unwrap_or(0),
+        };
+        let amount = naira
+            .checked_mul(KOBO_PER_NAIRA)
+            .and_then(|amount| amount.checked_add(kobo))
+            .ok_or(ParseMoneyError("amount out of range"))?;
+
+        Ok(Money(if negative { -amount } else { amount }))
+    }
+
+    /// A whole number of kobo sent as a float, e.g. `10000.0`.
+    fn from_kobo_f64(kobo: f64) -> Option<Money> {
+        if kobo.is_finite() && kobo.fract() == 0.0 && kobo.abs() < i64::MAX as f64 {
+            Some(Money(kobo as i64))
+        } else {
+            None
+        }
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: i64) -> Money {
+        Money(self.0 * quantity)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+/// Formats the amount in naira, e.g. `₦1,234.56`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            f.write_str("-")?;
+        }
+        let naira: Vec<char> = (self.0 / KOBO_PER_NAIRA)
+            .unsigned_abs()
+            .to_string()
+            .chars()
+            .collect();
+        let kobo = (self.0 % KOBO_PER_NAIRA).unsigned_abs();
+        let groups: Vec<String> = naira
+            .rchunks(3)
+            .rev()
+            .map(|group| group.iter().collect())
+            .collect();
+
+        write!(f, "₦{}.{:02}", groups.join(","), kobo)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMoneyError(&'static str);
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+impl serde::Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        deserializer.deserialize_any(KoboVisitor)
+    }
+}
+
+struct KoboVisitor;
+
+impl<'de> Visitor<'de> for KoboVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a whole number of kobo as a number or string")
+    }
+
+    fn visit_i64<E: de::Error>(self, kobo: i64) -> Result<Money, E> {
+        Ok(Money(kobo))
+    }
+
+    fn visit_u64<E: de::Error>(self, kobo: u64) -> Result<Money, E> {
+        if kobo > i64::MAX as u64 {
+            return Err(E::custom("amount out of range"));
+        }
+        Ok(Money(kobo as i64))
+    }
+
+    fn visit_f64<E: de::Error>(self, kobo: f64) -> Result<Money, E> {
+        Money::from_kobo_f64(kobo)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Float(kobo), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, kobo: &str) -> Result<Money, E> {
+        let kobo = kobo.trim();
+        match kobo.parse::<i64>() {
+            Ok(kobo) => Ok(Money(kobo)),
+            Err(_) => kobo
+                .parse::<f64>()
+                .ok()
+                .and_then(Money::from_kobo_f64)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(kobo), &self)),
+        }
+    }
+}
+
+/// Serde adapter for amounts Kuda sends as JSON numbers of kobo.
+///
+/// ```ignore
+/// #[serde(rename = "Amount", with = "crate::money::number")]
+/// pub amount: Money,
+/// ```
+pub mod number {
+    use super::Money;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(money: &Money, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(money.kobo())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// Serde adapter for optional amounts Kuda sends as JSON numbers of kobo.
+pub mod option {
+    use super::Money;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        money: &Option<Money>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match *money {
+            Some(ref money) => super::number::serialize(money, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Money>, D::Error> {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_conversions() {
+        let money = Money::from_naira(1234) + Money::from_kobo(56);
+
+        assert_eq!(money.kobo(), 123456);
+        assert_eq!(money.naira(), 1234);
+        assert_eq!(money.kobo_part(), 56);
+        assert_eq!(money - Money::from_kobo(56), Money::from_naira(1234));
+        assert_eq!(Money::from_kobo(250) * 4, Money::from_naira(10));
+        assert_eq!(
+            [Money::from_naira(1), Money::from_kobo(50)]
+                .iter()
+                .sum::<Money>(),
+            Money::from_kobo(150)
+        );
+        assert_eq!(
+            Money::from_kobo(i64::MAX).checked_add(Money::from_kobo(1)),
+            None
+        );
+        assert_eq!(Money::from_kobo(i64::MAX).checked_mul(2), None);
+        assert_eq!(Money::from_kobo(i64::MIN).checked_neg(), None);
+    }
+
+    #[test]
+    fn test_money_display() {
+        assert_eq!(Money::from_kobo(123456789).to_string(), "₦1,234,567.89");
+        assert_eq!(Money::from_kobo(5).to_string(), "₦0.05");
+        assert_eq!(Money::from_kobo(-100000).to_string(), "-₦1,000.00");
+    }
+
+    #[test]
+    fn test_money_from_naira_str() {
+        assert_eq!(
+            Money::from_naira_str("1234.56"),
+            Ok(Money::from_kobo(123456))
+        );
+        assert_eq!(
+            Money::from_naira_str("₦1,234.5"),
+            Ok(Money::from_kobo(123450))
+        );
+        assert_eq!(Money::from_naira_str("-20"), Ok(Money::from_naira(-20)));
+        assert!(Money::from_naira_str("12.345").is_err());
+        assert!(Money::from_naira_str("abc").is_err());
+    }
+
+    #[test]
+    fn test_money_serde() {
+        assert_eq!(
+            serde_json::to_string(&Money::from_kobo(10000)).unwrap(),
+            "\"10000\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Money>("\"10000\"").unwrap(),
+            Money::from_kobo(10000)
+        );
+        assert_eq!(
+            serde_json::from_str::<Money>("10000.0").unwrap(),
+            Money::from_kobo(10000)
+        );
+        assert!(serde_json::from_str::<Money>("\"1777860.4\"").is_err());
+        assert!(serde_json::from_str::<Money>("1.5").is_err());
+        assert!(serde_json::from_str::<Money>("\"ten\"").is_err());
+
+        #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+        struct Bill {
+            #[serde(with = "option", default)]
+            amount: Option<Money>,
+        }
+        let bill: Bill = serde_json::from_str(r#"{"amount":150000}"#).unwrap();
+        assert_eq!(bill.amount, Some(Money::from_naira(1500)));
+        assert_eq!(
+            serde_json::to_string(&bill).unwrap(),
+            r#"{"amount":150000}"#
+        );
+        let bill: Bill = serde_json::from_str(r#"{"amount":null}"#).unwrap();
+        assert_eq!(bill.amount, None);
+    }
+}
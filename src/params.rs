@@ -1,5 +1,7 @@
 use serde_derive::{Deserialize, Serialize};
 
+use crate::error::{Error, RequestError};
+
 #[derive(Clone, Default)]
 pub struct Headers {}
 
@@ -17,22 +19,269 @@ pub struct Request<T> {
 }
 
 /// An response wrapper.
+///
+/// Field names are accepted in both the PascalCase used by the v1 API and the
+/// camelCase used by the v2 API.
 #[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Response<T> {
-    #[serde(rename = "RequestReference")]
+    #[serde(rename = "RequestReference", alias = "requestReference")]
     pub request_reference: Option<String>,
-    #[serde(rename = "TransactionReference")]
+    #[serde(rename = "TransactionReference", alias = "transactionReference")]
     pub transaction_reference: Option<String>,
-    #[serde(rename = "ResponseCode")]
-    pub response_code: Option<String>,
+    #[serde(rename = "ResponseCode", alias = "responseCode")]
+    pub response_code: Option<ResponseCode>,
     /// Response status.
-    #[serde(rename = "Status")]
+    #[serde(rename = "Status", alias = "status")]
     pub status: bool,
     /// Response message.
-    #[serde(rename = "Message")]
+    #[serde(rename = "Message", alias = "message")]
     pub message: String,
     /// Data container.
-    #[serde(rename = "Data")]
+    #[serde(rename = "Data", alias = "data")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
 }
+
+impl<T> Response<T> {
+    /// Turns a response Kuda returned with `status: false` into an
+    /// `Error::Kuda` carrying its message, code and references.
+    ///
+    /// ```ignore
+    /// let response = AdminAccount::fund_transfer(&client, params).await?.into_result()?;
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn into_result(self) -> Result<Response<T>, Error> {
+        if self.status {
+            Ok(self)
+        } else {
+            Err(RequestError::rejected(&self).into())
+        }
+    }
+}
+
+/// The `ResponseCode` of a Kuda response.
+///
+/// Kuda passes NIBSS codes through for transfers and uses `-1` for requests it
+/// rejects itself. Codes without a variant are kept as `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ResponseCode {
+    /// `00`: the request succeeded.
+    Success,
+    /// `01`: the outcome is not known yet; wait for settlement.
+    StatusUnknown,
+    /// `-1`: Kuda rejected or cancelled the request.
+    Failed,
+    /// `03`: the sender is not valid.
+    InvalidSender,
+    /// `05`: the receiving bank declined the transfer.
+    DoNotHonor,
+    /// `06`: the account is dormant.
+    DormantAccount,
+    /// `07`: the account does not exist.
+    InvalidAccount,
+    /// `08`: the account name does not match.
+    AccountNameMismatch,
+    /// `09`: the request is still being processed.
+    InProgress,
+    /// `12`: the transaction is not valid.
+    InvalidTransaction,
+    /// `13`: the amount is not valid.
+    InvalidAmount,
+    /// `16`: the bank code is not known.
+    UnknownBankCode,
+    /// `25`: no record matches the request.
+    RecordNotFound,
+    /// `26`: a record with this reference already exists.
+    DuplicateReference,
+    /// `51`: the account has insufficient funds.
+    InsufficientFunds,
+    /// `57`: the sender is not permitted to make the transaction.
+    NotPermitted,
+    /// `61`: the transfer limit was exceeded.
+    LimitExceeded,
+    /// `91`: the beneficiary bank is not available.
+    BankUnavailable,
+    /// `94`: the transaction is a duplicate.
+    DuplicateTransaction,
+    /// `96`: a system malfunction.
+    SystemMalfunction,
+    /// `97`: the destination bank did not answer in time.
+    Timeout,
+    /// Any other code.
+    Unknown(String),
+}
+
+impl ResponseCode {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ResponseCode::Success => "00",
+            ResponseCode::StatusUnknown => "01",
+            ResponseCode::Failed => "-1",
+            ResponseCode::InvalidSender => "03",
+            ResponseCode::DoNotHonor => "05",
+            ResponseCode::DormantAccount => "06",
+            ResponseCode::InvalidAccount => "07",
+            ResponseCode::AccountNameMismatch => "08",
+            ResponseCode::InProgress => "09",
+            ResponseCode::InvalidTransaction => "12",
+            ResponseCode::InvalidAmount => "13",
+            ResponseCode::UnknownBankCode => "16",
+            ResponseCode::RecordNotFound => "25",
+            ResponseCode::DuplicateReference => "26",
+            ResponseCode::InsufficientFunds => "51",
+            ResponseCode::NotPermitted => "57",
+            ResponseCode::LimitExceeded => "61",
+            ResponseCode::BankUnavailable => "91",
+            ResponseCode::DuplicateTransaction => "94",
+            ResponseCode::SystemMalfunction => "96",
+            ResponseCode::Timeout => "97",
+            ResponseCode::Unknown(ref code) => code,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        *self == ResponseCode::Success
+    }
+
+    /// Whether the outcome may still change, e.g. a transfer awaiting
+    /// settlement. Its status should be checked again later.
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            *self,
+            ResponseCode::StatusUnknown | ResponseCode::InProgress | ResponseCode::Timeout
+        )
+    }
+
+    /// Whether the outcome is settled: the request succeeded or definitely
+    /// failed. Unknown codes are not assumed to be final.
+    pub fn is_final(&self) -> bool {
+        !self.is_pending() && !matches!(*self, ResponseCode::Unknown(_))
+    }
+
+    /// Whether the request failed without effect for a passing reason, so it
+    /// can be sent again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            *self,
+            ResponseCode::BankUnavailable | ResponseCode::SystemMalfunction
+        )
+    }
+}
+
+impl From<&str> for ResponseCode {
+    fn from(code: &str) -> ResponseCode {
+        match code {
+            "00" => ResponseCode::Success,
+            "01" => ResponseCode::StatusUnknown,
+            "-1" => ResponseCode::Failed,
+            "03" => ResponseCode::InvalidSender,
+            "05" => ResponseCode::DoNotHonor,
+            "06" => ResponseCode::DormantAccount,
+            "07" => ResponseCode::InvalidAccount,
+            "08" => ResponseCode::AccountNameMismatch,
+            "09" => ResponseCode::InProgress,
+            "12" => ResponseCode::InvalidTransaction,
+            "13" => ResponseCode::InvalidAmount,
+            "16" => ResponseCode::UnknownBankCode,
+            "25" => ResponseCode::RecordNotFound,
+            "26" => ResponseCode::DuplicateReference,
+            "51" => ResponseCode::InsufficientFunds,
+            "57" => ResponseCode::NotPermitted,
+            "61" => ResponseCode::LimitExceeded,
+            "91" => ResponseCode::BankUnavailable,
+            "94" => ResponseCode::DuplicateTransaction,
+            "96" => ResponseCode::SystemMalfunction,
+            "97" => ResponseCode::Timeout,
+            code => ResponseCode::Unknown(code.to_string()),
+        }
+    }
+}
+
+impl From<String> for ResponseCode {
+    fn from(code: String) -> ResponseCode {
+        ResponseCode::from(code.as_str())
+    }
+}
+
+impl From<ResponseCode> for String {
+    fn from(code: ResponseCode) -> String {
+        code.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for ResponseCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_code() {
+        let response: Response<()> =
+            serde_json::from_str(r#"{"ResponseCode":"51","Status":false,"Message":""}"#).unwrap();
+        assert_eq!(
+            response.response_code,
+            Some(ResponseCode::InsufficientFunds)
+        );
+
+        for code in &[
+            "00", "01", "-1", "06", "09", "26", "51", "91", "96", "97", "k99",
+        ] {
+            let parsed = ResponseCode::from(*code);
+            assert_eq!(parsed.as_str(), *code);
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                format!("\"{}\"", code)
+            );
+        }
+        assert_eq!(
+            ResponseCode::from("k99"),
+            ResponseCode::Unknown("k99".to_string())
+        );
+
+        assert!(ResponseCode::Success.is_final());
+        assert!(ResponseCode::InsufficientFunds.is_final());
+        assert!(!ResponseCode::StatusUnknown.is_final());
+        assert!(!ResponseCode::Unknown("k99".to_string()).is_final());
+        assert!(ResponseCode::SystemMalfunction.is_retryable());
+        assert!(!ResponseCode::Timeout.is_retryable());
+    }
+
+    #[test]
+    fn test_into_result() {
+        let response: Response<()> = Response {
+            status: true,
+            ..Default::default()
+        };
+        assert!(response.into_result().is_ok());
+
+        let response: Response<()> = serde_json::from_str(
+            r#"{
+                "RequestReference": "SP-1",
+                "TransactionReference": "000000000000000000000000001",
+                "ResponseCode": "51",
+                "Status": false,
+                "Message": "Insufficient funds"
+            }"#,
+        )
+        .unwrap();
+        match response.into_result() {
+            Err(Error::Kuda(err)) => {
+                assert_eq!(err.message, "Insufficient funds");
+                assert_eq!(err.response_code, Some(ResponseCode::InsufficientFunds));
+                assert_eq!(err.request_reference.as_deref(), Some("SP-1"));
+                assert_eq!(
+                    err.transaction_reference.as_deref(),
+                    Some("000000000000000000000000001")
+                );
+                assert_eq!(err.to_string(), "(200) [51] Insufficient funds");
+            }
+            other => panic!("expected Error::Kuda, got {:?}", other),
+        }
+    }
+}
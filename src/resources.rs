@@ -1,6 +1,15 @@
 pub use self::accounts::*;
 pub use self::banks::*;
+pub use self::bills::*;
+pub use self::cards::*;
+pub use self::savings::*;
+pub use self::transfers::*;
 
 // Core Resources
 mod accounts;
 mod banks;
+mod bills;
+mod cards;
+mod pagination;
+mod savings;
+mod transfers;
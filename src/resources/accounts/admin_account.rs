@@ -1,8 +1,13 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use serde_derive::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 use std::str;
 
-use crate::config::{Client, Response};
+use crate::client::transport::KudaTransport;
+use crate::config::{Client, ResponseStream};
+use crate::money::Money;
 use crate::params::Response as R;
+use crate::resources::pagination::{paginate, Page};
 
 pub struct AdminAccount {}
 
@@ -25,12 +30,12 @@ impl AdminAccountBalanceRequest {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AdminAccountGetBalanceResponseData {
-    #[serde(rename = "LedgerBalance")]
-    pub ledger_balance: f64,
-    #[serde(rename = "AvailableBalance")]
-    pub available_balance: f64,
-    #[serde(rename = "WithdrawableBalance")]
-    pub withdrawable_balance: f64,
+    #[serde(rename = "LedgerBalance", with = "crate::money::number")]
+    pub ledger_balance: Money,
+    #[serde(rename = "AvailableBalance", with = "crate::money::number")]
+    pub available_balance: Money,
+    #[serde(rename = "WithdrawableBalance", with = "crate::money::number")]
+    pub withdrawable_balance: Money,
 }
 
 /// The parameters for `Account::request::enquiry`.
@@ -73,8 +78,8 @@ pub struct AccountEnquiryResponseData {
     pub name_enquiry_id: i64,
     #[serde(rename = "ResponseCode")]
     pub response_code: String,
-    #[serde(rename = "TransferCharge")]
-    pub transfer_charge: f64,
+    #[serde(rename = "TransferCharge", with = "crate::money::number")]
+    pub transfer_charge: Money,
     #[serde(rename = "SessionID")]
     pub session_id: String,
 }
@@ -93,7 +98,7 @@ pub struct AdminAccountFundTransferRequest {
     #[serde(rename = "beneficiaryName")]
     pub beneficiary_name: String,
     /// Amount to be transferred. All amounts in kobo.
-    pub amount: String,
+    pub amount: Money,
     /// Transaction narration.
     pub narration: String,
     /// Session ID generated from the nameEnquiry request.
@@ -120,6 +125,174 @@ impl AdminAccountFundTransferRequest {
     }
 }
 
+/// The data of a `fund_transfer` or `fund_transfer_virtual_account` response.
+///
+/// Kuda often sends no data for a transfer; its references are then only on
+/// the response itself.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FundTransferResponseData {
+    #[serde(
+        rename = "TransactionReference",
+        alias = "transactionReference",
+        default
+    )]
+    pub transaction_reference: Option<String>,
+    /// NIBSS session ID of the transfer.
+    #[serde(
+        rename = "SessionID",
+        alias = "sessionId",
+        alias = "SessionId",
+        default
+    )]
+    pub session_id: Option<String>,
+    #[serde(rename = "Status", alias = "status", default)]
+    pub status: Option<TransferStatus>,
+    /// Fee charged for the transfer. All amounts in kobo.
+    #[serde(
+        rename = "TransferCharge",
+        alias = "transferCharge",
+        alias = "Charge",
+        with = "crate::money::option",
+        default
+    )]
+    pub charge: Option<Money>,
+    #[serde(
+        rename = "TransactionDate",
+        alias = "transactionDate",
+        with = "crate::datetime::option",
+        default
+    )]
+    pub transaction_date: Option<NaiveDateTime>,
+}
+
+/// The data of a `check_transfer_status` response.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferStatusResponseData {
+    /// The `requestRef` the transfer was sent with.
+    #[serde(rename = "RequestReference", alias = "requestReference", default)]
+    pub request_reference: Option<String>,
+    #[serde(
+        rename = "TransactionReference",
+        alias = "transactionReference",
+        default
+    )]
+    pub transaction_reference: Option<String>,
+    /// NIBSS session ID of the transfer.
+    #[serde(
+        rename = "SessionID",
+        alias = "sessionId",
+        alias = "SessionId",
+        default
+    )]
+    pub session_id: Option<String>,
+    #[serde(rename = "Status", alias = "status", default)]
+    pub status: Option<TransferStatus>,
+    #[serde(
+        rename = "Amount",
+        alias = "amount",
+        with = "crate::money::option",
+        default
+    )]
+    pub amount: Option<Money>,
+    /// Fee charged for the transfer. All amounts in kobo.
+    #[serde(
+        rename = "TransferCharge",
+        alias = "transferCharge",
+        alias = "Charge",
+        with = "crate::money::option",
+        default
+    )]
+    pub charge: Option<Money>,
+    #[serde(
+        rename = "TransactionDate",
+        alias = "transactionDate",
+        with = "crate::datetime::option",
+        default
+    )]
+    pub transaction_date: Option<NaiveDateTime>,
+    /// When the transfer settled, if it has.
+    #[serde(
+        rename = "CompletionDate",
+        alias = "completionDate",
+        with = "crate::datetime::option",
+        default
+    )]
+    pub completion_date: Option<NaiveDateTime>,
+}
+
+/// The status of a transfer, as reported by Kuda.
+///
+/// Statuses without a variant are kept as `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum TransferStatus {
+    /// The beneficiary has been credited.
+    Successful,
+    /// The transfer is still being processed.
+    Pending,
+    /// The transfer failed and the sender was not debited.
+    Failed,
+    /// The transfer failed after the sender was debited, and was refunded.
+    Reversed,
+    /// Any other status.
+    Unknown(String),
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            TransferStatus::Successful => "Successful",
+            TransferStatus::Pending => "Pending",
+            TransferStatus::Failed => "Failed",
+            TransferStatus::Reversed => "Reversed",
+            TransferStatus::Unknown(ref status) => status,
+        }
+    }
+
+    pub fn is_successful(&self) -> bool {
+        *self == TransferStatus::Successful
+    }
+
+    /// Whether the status is settled. Unknown statuses are not assumed to be
+    /// final.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            *self,
+            TransferStatus::Successful | TransferStatus::Failed | TransferStatus::Reversed
+        )
+    }
+}
+
+impl From<&str> for TransferStatus {
+    fn from(status: &str) -> TransferStatus {
+        match status.to_ascii_lowercase().as_str() {
+            "successful" | "success" | "completed" => TransferStatus::Successful,
+            "pending" | "processing" | "inprogress" => TransferStatus::Pending,
+            "failed" => TransferStatus::Failed,
+            "reversed" => TransferStatus::Reversed,
+            _ => TransferStatus::Unknown(status.to_string()),
+        }
+    }
+}
+
+impl From<String> for TransferStatus {
+    fn from(status: String) -> TransferStatus {
+        TransferStatus::from(status.as_str())
+    }
+}
+
+impl From<TransferStatus> for String {
+    fn from(status: TransferStatus) -> String {
+        status.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The parameters for `AdminAccount::request::transaction_history`.
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -151,10 +324,10 @@ pub struct AdminAccountTransactionHistoryResponseData {
     pub status_code: String,
     #[serde(rename = "TotalRecordInStore")]
     pub total_record_in_store: i64,
-    #[serde(rename = "TotalDebit")]
-    pub total_debit: f64,
-    #[serde(rename = "TotalCredit")]
-    pub total_credit: f64,
+    #[serde(rename = "TotalDebit", with = "crate::money::number")]
+    pub total_debit: Money,
+    #[serde(rename = "TotalCredit", with = "crate::money::number")]
+    pub total_credit: Money,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -168,14 +341,14 @@ pub struct AdminAccountTransactionHistoryPostingsHistory {
     pub account_number: String,
     #[serde(rename = "LinkedAccountNumber")]
     pub linked_account_number: ::serde_json::Value,
-    #[serde(rename = "RealDate")]
-    pub real_date: String,
-    #[serde(rename = "Amount")]
-    pub amount: f64,
-    #[serde(rename = "OpeningBalance")]
-    pub opening_balance: f64,
-    #[serde(rename = "BalanceAfter")]
-    pub balance_after: f64,
+    #[serde(rename = "RealDate", with = "crate::datetime::naive")]
+    pub real_date: NaiveDateTime,
+    #[serde(rename = "Amount", with = "crate::money::number")]
+    pub amount: Money,
+    #[serde(rename = "OpeningBalance", with = "crate::money::number")]
+    pub opening_balance: Money,
+    #[serde(rename = "BalanceAfter", with = "crate::money::number")]
+    pub balance_after: Money,
     #[serde(rename = "Narration")]
     pub narration: String,
     #[serde(rename = "InstrumentNumber")]
@@ -186,14 +359,26 @@ pub struct AdminAccountTransactionHistoryPostingsHistory {
     pub posted_by: String,
 }
 
+impl Page for AdminAccountTransactionHistoryResponseData {
+    type Item = AdminAccountTransactionHistoryPostingsHistory;
+
+    fn into_records(self) -> (Vec<Self::Item>, i64) {
+        (self.postings_history, self.total_record_in_store)
+    }
+}
+
 /// The parameters for `AdminAccount::request::filtered_transaction_history`.
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AdminAccountFilteredTransactionHistoryRequest {
     /// Unique identifier for the account.
     pub tracking_reference: String,
-    pub start_date: String,
-    pub end_date: String,
+    /// Start of the period, inclusive.
+    #[serde(with = "crate::datetime::naive")]
+    pub start_date: NaiveDateTime,
+    /// End of the period, inclusive.
+    #[serde(with = "crate::datetime::naive")]
+    pub end_date: NaiveDateTime,
     pub page_size: i64,
     pub page_number: i64,
 }
@@ -208,6 +393,13 @@ impl AdminAccountFilteredTransactionHistoryRequest {
             page_number: Default::default(),
         }
     }
+
+    /// Limits the history to the transactions made on the days in `dates`.
+    pub fn set_dates(&mut self, dates: RangeInclusive<NaiveDate>) {
+        let (start_date, end_date) = crate::datetime::bounds(dates);
+        self.start_date = start_date;
+        self.end_date = end_date;
+    }
 }
 
 /// The parameters for `AdminAccount::request::check_transfer_status`.
@@ -230,50 +422,91 @@ impl AdminAccountCheckTransferRequest {
 
 impl AdminAccount {
     /// Get Admin Account Balance.
-    pub fn get_admin_account_balance(
-        client: &Client,
+    pub fn get_admin_account_balance<C: KudaTransport>(
+        client: &C,
         params: AdminAccountBalanceRequest,
-    ) -> Response<R<AdminAccountGetBalanceResponseData>> {
+    ) -> C::Response<R<AdminAccountGetBalanceResponseData>> {
         client.post_body("/v1", "ADMIN_RETRIEVE_MAIN_ACCOUNT_BALANCE", params)
     }
 
     /// Retrieve a list of all transactions for a main account.
-    pub fn get_admin_account_transaction_history(
-        client: &Client,
+    pub fn get_admin_account_transaction_history<C: KudaTransport>(
+        client: &C,
         params: AdminAccountTransactionHistoryRequest,
-    ) -> Response<R<AdminAccountTransactionHistoryResponseData>> {
+    ) -> C::Response<R<AdminAccountTransactionHistoryResponseData>> {
         client.post_body("/v1", "ADMIN_MAIN_ACCOUNT_TRANSACTIONS", params)
     }
 
     /// Retrieve a list of filtered transactions for a main account.
-    pub fn get_admin_account_filtered_transaction_history(
-        client: &Client,
+    pub fn get_admin_account_filtered_transaction_history<C: KudaTransport>(
+        client: &C,
         params: AdminAccountFilteredTransactionHistoryRequest,
-    ) -> Response<R<AdminAccountTransactionHistoryResponseData>> {
+    ) -> C::Response<R<AdminAccountTransactionHistoryResponseData>> {
         client.post_body("/v1", "ADMIN_MAIN_ACCOUNT_FILTERED_TRANSACTIONS", params)
     }
 
-    /// Confirm Transfer Recipient.
-    pub fn enquire_bank_account(
+    /// Stream every transaction for a main account, fetching pages as needed.
+    ///
+    /// Paging starts at `params.page_number` and fetches `params.page_size`
+    /// transactions at a time.
+    ///
+    /// Async only: streams need the async [`Client`] rather than any
+    /// `KudaTransport`. With the blocking client, page through the history
+    /// call instead.
+    pub fn transactions_stream(
+        client: &Client,
+        params: AdminAccountTransactionHistoryRequest,
+    ) -> ResponseStream<AdminAccountTransactionHistoryPostingsHistory> {
+        let client = client.clone();
+        let (page_number, page_size) = (params.page_number, params.page_size);
+        paginate(page_number, page_size, move |page_number, page_size| {
+            let mut params = params.clone();
+            params.page_number = page_number;
+            params.page_size = page_size;
+            AdminAccount::get_admin_account_transaction_history(&client, params)
+        })
+    }
+
+    /// Stream every filtered transaction for a main account, fetching pages as needed.
+    ///
+    /// Async only: streams need the async [`Client`] rather than any
+    /// `KudaTransport`. With the blocking client, page through the history
+    /// call instead.
+    pub fn filtered_transactions_stream(
         client: &Client,
+        params: AdminAccountFilteredTransactionHistoryRequest,
+    ) -> ResponseStream<AdminAccountTransactionHistoryPostingsHistory> {
+        let client = client.clone();
+        let (page_number, page_size) = (params.page_number, params.page_size);
+        paginate(page_number, page_size, move |page_number, page_size| {
+            let mut params = params.clone();
+            params.page_number = page_number;
+            params.page_size = page_size;
+            AdminAccount::get_admin_account_filtered_transaction_history(&client, params)
+        })
+    }
+
+    /// Confirm Transfer Recipient.
+    pub fn enquire_bank_account<C: KudaTransport>(
+        client: &C,
         params: AccountEnquiryRequest,
-    ) -> Response<R<AccountEnquiryResponseData>> {
+    ) -> C::Response<R<AccountEnquiryResponseData>> {
         client.post_body("/v1", "NAME_ENQUIRY", params)
     }
 
     /// Send Money from a Kuda Account.
-    pub fn fund_transfer(
-        client: &Client,
+    pub fn fund_transfer<C: KudaTransport>(
+        client: &C,
         params: AdminAccountFundTransferRequest,
-    ) -> Response<R<serde_json::Value>> {
+    ) -> C::Response<R<FundTransferResponseData>> {
         client.post_body("/v1", "SINGLE_FUND_TRANSFER", params)
     }
 
     /// Check the status of a bank transfer.
-    pub fn check_transfer_status(
-        client: &Client,
+    pub fn check_transfer_status<C: KudaTransport>(
+        client: &C,
         params: AdminAccountCheckTransferRequest,
-    ) -> Response<R<serde_json::Value>> {
+    ) -> C::Response<R<TransferStatusResponseData>> {
         client.post_body("/v1", "TRANSACTION_STATUS_QUERY", params)
     }
 }
@@ -281,20 +514,25 @@ impl AdminAccount {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockServer;
+    use crate::params::ResponseCode;
     use crate::Error;
 
-    const PUBLIC_KEY: &str = "CHANGE_ME";
+    const FUND_TRANSFER: &str =
+        include_str!("../../../tests/fixtures/transfers/fund_transfer.json");
+    const TRANSFER_STATUS: &str =
+        include_str!("../../../tests/fixtures/transfers/transfer_status.json");
+    const TRANSFER_STATUS_PENDING: &str =
+        include_str!("../../../tests/fixtures/transfers/transfer_status_pending.json");
 
-    const PRIVATE_KEY: &str = "CHANGE_ME";
+    fn date(s: &str) -> NaiveDateTime {
+        crate::datetime::parse(s).unwrap()
+    }
 
     #[tokio::test]
     async fn test_get_admin_account_balance() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AdminAccountBalanceRequest::new();
         params.tracking_reference = "0012".to_string();
@@ -309,18 +547,14 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Operation successful");
     }
 
     #[tokio::test]
     async fn test_get_admin_account_transaction_history() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AdminAccountTransactionHistoryRequest::new();
         params.tracking_reference = "0012".to_string();
@@ -337,23 +571,19 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
     }
 
     #[tokio::test]
     async fn test_get_admin_account_filtered_transaction_history() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AdminAccountFilteredTransactionHistoryRequest::new();
         params.tracking_reference = "0012".to_string();
-        params.start_date = "2021-05-24T00:00:00".to_string();
-        params.end_date = "2021-05-24T23:59:59".to_string();
+        let day = NaiveDate::from_ymd_opt(2021, 5, 24).unwrap();
+        params.set_dates(day..=day);
         params.page_number = 1;
         params.page_size = 100;
 
@@ -367,18 +597,14 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
     }
 
     #[tokio::test]
     async fn test_enquire_bank_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AccountEnquiryRequest::new();
         params.beneficiary_account_number = "0012".to_string();
@@ -394,30 +620,26 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
     }
 
     #[tokio::test]
     async fn test_admin_account_fund_transfer() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AdminAccountFundTransferRequest::new();
         params.beneficiary_account = "0012".to_string();
         params.beneficiary_bank_code = "0012".to_string();
         params.beneficiary_name = "0012".to_string();
-        params.amount = "10000".to_string();
+        params.amount = Money::from_kobo(10000);
         params.narration = "test fund transfer".to_string();
         params.name_enquiry_session_id = "0".to_string();
         params.tracking_reference = "0012".to_string();
         params.sender_name = "test sender".to_string();
 
-        let response: Result<R<serde_json::Value>, Error> =
+        let response: Result<R<FundTransferResponseData>, Error> =
             AdminAccount::fund_transfer(&client, params).await;
         let account = match response {
             Err(err) => panic!("{:?}", err),
@@ -427,24 +649,23 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Transaction successful.");
+        let data = account.data.unwrap();
+        assert_eq!(data.status, Some(TransferStatus::Successful));
+        assert_eq!(data.transaction_reference, account.transaction_reference);
     }
 
     #[tokio::test]
     async fn test_check_transfer_status() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut params = AdminAccountCheckTransferRequest::new();
         params.is_third_party_bank_transfer = true;
         params.transaction_request_reference = "SP-SBI8IIU".to_string();
 
-        let response: Result<R<serde_json::Value>, Error> =
+        let response: Result<R<TransferStatusResponseData>, Error> =
             AdminAccount::check_transfer_status(&client, params).await;
         let account = match response {
             Err(err) => panic!("{:?}", err),
@@ -454,7 +675,70 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "RecordNotFound");
     }
+
+    #[test]
+    fn test_fund_transfer_response() {
+        let response: R<FundTransferResponseData> = serde_json::from_str(FUND_TRANSFER).unwrap();
+        assert_eq!(response.response_code, Some(ResponseCode::Success));
+
+        let data = response.data.unwrap();
+        assert_eq!(
+            data.transaction_reference.as_deref(),
+            Some("210528024512893245611209873")
+        );
+        assert_eq!(
+            data.session_id.as_deref(),
+            Some("090267210528144512000098765432")
+        );
+        assert_eq!(data.status, Some(TransferStatus::Successful));
+        assert_eq!(data.charge, Some(Money::from_naira(10)));
+        assert_eq!(data.transaction_date, Some(date("2021-05-28T14:45:12.893")));
+    }
+
+    #[test]
+    fn test_transfer_status_response() {
+        let response: R<TransferStatusResponseData> =
+            serde_json::from_str(TRANSFER_STATUS).unwrap();
+        let data = response.data.unwrap();
+        assert_eq!(
+            data.request_reference.as_deref(),
+            Some("SP-C2N3FQQ8UB0AVQ1C9LPG")
+        );
+        assert!(data.status.as_ref().unwrap().is_successful());
+        assert_eq!(data.amount, Some(Money::from_naira(1000)));
+        assert_eq!(data.charge, Some(Money::from_naira(10)));
+        assert_eq!(data.completion_date, Some(date("2021-05-28T14:45:20.117")));
+
+        let response: R<TransferStatusResponseData> =
+            serde_json::from_str(TRANSFER_STATUS_PENDING).unwrap();
+        assert_eq!(response.response_code, Some(ResponseCode::StatusUnknown));
+        let data = response.data.unwrap();
+        assert_eq!(data.status, Some(TransferStatus::Pending));
+        assert!(!data.status.unwrap().is_final());
+        assert_eq!(data.session_id, None);
+        assert_eq!(data.amount, Some(Money::from_naira(2500)));
+        assert_eq!(data.completion_date, None);
+    }
+
+    #[test]
+    fn test_transfer_status() {
+        for status in &["Successful", "Pending", "Failed", "Reversed", "OnHold"] {
+            let parsed = TransferStatus::from(*status);
+            assert_eq!(parsed.as_str(), *status);
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                format!("\"{}\"", status)
+            );
+        }
+        assert_eq!(TransferStatus::from("SUCCESS"), TransferStatus::Successful);
+        assert_eq!(
+            TransferStatus::from("OnHold"),
+            TransferStatus::Unknown("OnHold".to_string())
+        );
+        assert!(TransferStatus::Reversed.is_final());
+        assert!(!TransferStatus::Unknown("OnHold".to_string()).is_final());
+    }
 }
@@ -1,8 +1,14 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use serde_derive::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 use std::str;
 
-use crate::config::{Client, Response};
+use crate::client::transport::KudaTransport;
+use crate::config::{Client, ResponseStream};
+use crate::money::Money;
 use crate::params::Response as R;
+use crate::resources::pagination::{paginate, Page};
+use crate::resources::FundTransferResponseData;
 
 pub struct VirtualAccount {}
 
@@ -80,19 +86,19 @@ pub struct VirtualAccountGetResponseAccount {
     pub account_name: String,
     #[serde(rename = "TrackingReference")]
     pub tracking_reference: String,
-    #[serde(rename = "CreationDate")]
-    pub creation_date: String,
+    #[serde(rename = "CreationDate", with = "crate::datetime::naive")]
+    pub creation_date: NaiveDateTime,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VirtualAccountGetBalanceResponseData {
-    #[serde(rename = "LedgerBalance")]
-    pub ledger_balance: f64,
-    #[serde(rename = "AvailableBalance")]
-    pub available_balance: f64,
-    #[serde(rename = "WithdrawableBalance")]
-    pub withdrawable_balance: f64,
+    #[serde(rename = "LedgerBalance", with = "crate::money::number")]
+    pub ledger_balance: Money,
+    #[serde(rename = "AvailableBalance", with = "crate::money::number")]
+    pub available_balance: Money,
+    #[serde(rename = "WithdrawableBalance", with = "crate::money::number")]
+    pub withdrawable_balance: Money,
 }
 
 /// The parameters for `VirtualAccount::request::fund/withdraw`.
@@ -103,7 +109,7 @@ pub struct VirtualAccountFundWithdrawRequest {
     #[serde(rename = "TrackingReference")]
     pub tracking_reference: String,
     /// Amount to be funded/withdrawn. All amounts in kobo.
-    pub amount: String,
+    pub amount: Money,
     /// Transaction narration.
     pub narration: String,
 }
@@ -128,7 +134,7 @@ pub struct VirtualAccountFundTransferRequest {
     #[serde(rename = "beneficiaryAccount")]
     pub beneficiary_account: String,
     /// Amount to be transferred. All amounts in kobo.
-    pub amount: String,
+    pub amount: Money,
     /// Description of the transaction.
     pub narration: String,
     /// Bank code for the destination bank.
@@ -191,10 +197,10 @@ pub struct VirtualAccountTransactionHistoryResponseData {
     pub status_code: String,
     #[serde(rename = "TotalRecordInStore")]
     pub total_record_in_store: i64,
-    #[serde(rename = "TotalDebit")]
-    pub total_debit: f64,
-    #[serde(rename = "TotalCredit")]
-    pub total_credit: f64,
+    #[serde(rename = "TotalDebit", with = "crate::money::number")]
+    pub total_debit: Money,
+    #[serde(rename = "TotalCredit", with = "crate::money::number")]
+    pub total_credit: Money,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -208,14 +214,14 @@ pub struct VirtualAccountTransactionHistoryPostingsHistory {
     pub account_number: String,
     #[serde(rename = "LinkedAccountNumber")]
     pub linked_account_number: ::serde_json::Value,
-    #[serde(rename = "RealDate")]
-    pub real_date: String,
-    #[serde(rename = "Amount")]
-    pub amount: f64,
-    #[serde(rename = "OpeningBalance")]
-    pub opening_balance: f64,
-    #[serde(rename = "BalanceAfter")]
-    pub balance_after: f64,
+    #[serde(rename = "RealDate", with = "crate::datetime::naive")]
+    pub real_date: NaiveDateTime,
+    #[serde(rename = "Amount", with = "crate::money::number")]
+    pub amount: Money,
+    #[serde(rename = "OpeningBalance", with = "crate::money::number")]
+    pub opening_balance: Money,
+    #[serde(rename = "BalanceAfter", with = "crate::money::number")]
+    pub balance_after: Money,
     #[serde(rename = "Narration")]
     pub narration: String,
     #[serde(rename = "InstrumentNumber")]
@@ -226,14 +232,26 @@ pub struct VirtualAccountTransactionHistoryPostingsHistory {
     pub posted_by: String,
 }
 
+impl Page for VirtualAccountTransactionHistoryResponseData {
+    type Item = VirtualAccountTransactionHistoryPostingsHistory;
+
+    fn into_records(self) -> (Vec<Self::Item>, i64) {
+        (self.postings_history, self.total_record_in_store)
+    }
+}
+
 /// The parameters for `VirtualAccount::request::filtered_transaction_history`.
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VirtualAccountFilteredTransactionHistoryRequest {
     /// Unique identifier for the account.
     pub tracking_reference: String,
-    pub start_date: String,
-    pub end_date: String,
+    /// Start of the period, inclusive.
+    #[serde(with = "crate::datetime::naive")]
+    pub start_date: NaiveDateTime,
+    /// End of the period, inclusive.
+    #[serde(with = "crate::datetime::naive")]
+    pub end_date: NaiveDateTime,
     pub page_size: i64,
     pub page_number: i64,
 }
@@ -248,92 +266,222 @@ impl VirtualAccountFilteredTransactionHistoryRequest {
             page_number: Default::default(),
         }
     }
+
+    /// Limits the history to the transactions made on the days in `dates`.
+    pub fn set_dates(&mut self, dates: RangeInclusive<NaiveDate>) {
+        let (start_date, end_date) = crate::datetime::bounds(dates);
+        self.start_date = start_date;
+        self.end_date = end_date;
+    }
+}
+
+/// The parameters for `VirtualAccount::request::update`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualAccountUpdateRequest {
+    /// Unique identifier for the account.
+    pub tracking_reference: String,
+    /// User's last name.
+    pub last_name: String,
+    /// User's first name.
+    pub first_name: String,
+    /// User's email address.
+    pub email: String,
+}
+
+impl VirtualAccountUpdateRequest {
+    pub fn new() -> Self {
+        VirtualAccountUpdateRequest {
+            tracking_reference: Default::default(),
+            last_name: Default::default(),
+            first_name: Default::default(),
+            email: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualAccountStatusResponseData {
+    #[serde(rename = "AccountNumber")]
+    pub account_number: String,
+}
+
+/// The parameters for `VirtualAccount::request::list`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualAccountListRequest {
+    pub page_size: i64,
+    pub page_number: i64,
+}
+
+impl VirtualAccountListRequest {
+    pub fn new() -> Self {
+        VirtualAccountListRequest {
+            page_size: Default::default(),
+            page_number: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualAccountListResponseData {
+    #[serde(rename = "Accounts")]
+    pub accounts: Vec<VirtualAccountGetResponseAccount>,
+    #[serde(rename = "TotalCount")]
+    pub total_count: i64,
 }
 
 impl VirtualAccount {
     /// Create Virtual Account.
-    pub fn create_virtual_account(
-        client: &Client,
+    pub fn create_virtual_account<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountCreateRequest,
-    ) -> Response<R<VirtualAccountCreateResponseData>> {
+    ) -> C::Response<R<VirtualAccountCreateResponseData>> {
         client.post_body("/v1", "ADMIN_CREATE_VIRTUAL_ACCOUNT", Some(params))
     }
 
     /// Get Virtual Account.
-    pub fn get_virtual_account(
-        client: &Client,
+    pub fn get_virtual_account<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountGetRequest,
-    ) -> Response<R<VirtualAccountGetResponseData>> {
+    ) -> C::Response<R<VirtualAccountGetResponseData>> {
         client.post_body("/v1", "ADMIN_RETRIEVE_SINGLE_VIRTUAL_ACCOUNT", Some(params))
     }
 
     /// Get Virtual Account Balance.
-    pub fn get_virtual_account_balance(
-        client: &Client,
+    pub fn get_virtual_account_balance<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountGetRequest,
-    ) -> Response<R<VirtualAccountGetBalanceResponseData>> {
+    ) -> C::Response<R<VirtualAccountGetBalanceResponseData>> {
         client.post_body("/v1", "RETRIEVE_VIRTUAL_ACCOUNT_BALANCE", Some(params))
     }
 
     /// Retrieve a list of all transactions for a specified virtual account.
-    pub fn get_virtual_account_transaction_history(
-        client: &Client,
+    pub fn get_virtual_account_transaction_history<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountTransactionHistoryRequest,
-    ) -> Response<R<VirtualAccountTransactionHistoryResponseData>> {
+    ) -> C::Response<R<VirtualAccountTransactionHistoryResponseData>> {
         client.post_body("/v1", "ADMIN_VIRTUAL_ACCOUNT_TRANSACTIONS", params)
     }
 
     /// Retrieve a list of filtered transactions for a specified virtual account.
-    pub fn get_virtual_account_filtered_transaction_history(
-        client: &Client,
+    pub fn get_virtual_account_filtered_transaction_history<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountFilteredTransactionHistoryRequest,
-    ) -> Response<R<VirtualAccountTransactionHistoryResponseData>> {
+    ) -> C::Response<R<VirtualAccountTransactionHistoryResponseData>> {
         client.post_body("/v1", "ADMIN_VIRTUAL_ACCOUNT_FILTERED_TRANSACTIONS", params)
     }
 
-    /// Fund Virtual Account.
-    pub fn fund_virtual_account(
+    /// Stream every transaction for a specified virtual account, fetching pages as needed.
+    ///
+    /// Paging starts at `params.page_number` and fetches `params.page_size`
+    /// transactions at a time.
+    ///
+    /// Async only: streams need the async [`Client`] rather than any
+    /// `KudaTransport`. With the blocking client, page through the history
+    /// call instead.
+    pub fn transactions_stream(
         client: &Client,
+        params: VirtualAccountTransactionHistoryRequest,
+    ) -> ResponseStream<VirtualAccountTransactionHistoryPostingsHistory> {
+        let client = client.clone();
+        let (page_number, page_size) = (params.page_number, params.page_size);
+        paginate(page_number, page_size, move |page_number, page_size| {
+            let mut params = params.clone();
+            params.page_number = page_number;
+            params.page_size = page_size;
+            VirtualAccount::get_virtual_account_transaction_history(&client, params)
+        })
+    }
+
+    /// Stream every filtered transaction for a specified virtual account, fetching pages as needed.
+    ///
+    /// Async only: streams need the async [`Client`] rather than any
+    /// `KudaTransport`. With the blocking client, page through the history
+    /// call instead.
+    pub fn filtered_transactions_stream(
+        client: &Client,
+        params: VirtualAccountFilteredTransactionHistoryRequest,
+    ) -> ResponseStream<VirtualAccountTransactionHistoryPostingsHistory> {
+        let client = client.clone();
+        let (page_number, page_size) = (params.page_number, params.page_size);
+        paginate(page_number, page_size, move |page_number, page_size| {
+            let mut params = params.clone();
+            params.page_number = page_number;
+            params.page_size = page_size;
+            VirtualAccount::get_virtual_account_filtered_transaction_history(&client, params)
+        })
+    }
+
+    /// Fund Virtual Account.
+    pub fn fund_virtual_account<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountFundWithdrawRequest,
-    ) -> Response<R<serde_json::Value>> {
+    ) -> C::Response<R<serde_json::Value>> {
         client.post_body("/v1", "FUND_VIRTUAL_ACCOUNT", Some(params))
     }
 
     /// Withdraw Virtual Account.
-    pub fn withdraw_virtual_account(
-        client: &Client,
+    pub fn withdraw_virtual_account<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountFundWithdrawRequest,
-    ) -> Response<R<serde_json::Value>> {
+    ) -> C::Response<R<serde_json::Value>> {
         client.post_body("/v1", "WITHDRAW_VIRTUAL_ACCOUNT", Some(params))
     }
 
     /// Fund Transfer Virtual Account.
-    pub fn fund_transfer_virtual_account(
-        client: &Client,
+    pub fn fund_transfer_virtual_account<C: KudaTransport>(
+        client: &C,
         params: VirtualAccountFundTransferRequest,
-    ) -> Response<R<serde_json::Value>> {
+    ) -> C::Response<R<FundTransferResponseData>> {
         client.post_body("/v1", "VIRTUAL_ACCOUNT_FUND_TRANSFER", Some(params))
     }
+
+    /// Update Virtual Account.
+    pub fn update_virtual_account<C: KudaTransport>(
+        client: &C,
+        params: VirtualAccountUpdateRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "UPDATE_VIRTUAL_ACCOUNT", Some(params))
+    }
+
+    /// Enable Virtual Account.
+    pub fn enable_virtual_account<C: KudaTransport>(
+        client: &C,
+        params: VirtualAccountGetRequest,
+    ) -> C::Response<R<VirtualAccountStatusResponseData>> {
+        client.post_body("/v1", "ADMIN_ENABLE_VIRTUAL_ACCOUNT", Some(params))
+    }
+
+    /// Disable Virtual Account.
+    pub fn disable_virtual_account<C: KudaTransport>(
+        client: &C,
+        params: VirtualAccountGetRequest,
+    ) -> C::Response<R<VirtualAccountStatusResponseData>> {
+        client.post_body("/v1", "ADMIN_DISABLE_VIRTUAL_ACCOUNT", Some(params))
+    }
+
+    /// Retrieve a page of all virtual accounts.
+    pub fn list_virtual_accounts<C: KudaTransport>(
+        client: &C,
+        params: VirtualAccountListRequest,
+    ) -> C::Response<R<VirtualAccountListResponseData>> {
+        client.post_body("/v1", "ADMIN_VIRTUAL_ACCOUNTS", Some(params))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockServer;
     use crate::Error;
     use rand::Rng;
 
-    const PUBLIC_KEY: &str = "CHANGE_ME";
-
-    const PRIVATE_KEY: &str = "CHANGE_ME";
-
     #[tokio::test]
     async fn test_create_virtual_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let mut rng = rand::thread_rng();
 
@@ -341,7 +489,7 @@ mod tests {
         params.first_name = "Mark".to_string();
         params.last_name = "Smith".to_string();
         params.phone_number = "09039658058".to_string();
-        params.tracking_reference = generat;
+        params.tracking_reference = rng.gen::<u32>().to_string();
         params.email = "example@email.com".to_string();
 
         let response: Result<R<VirtualAccountCreateResponseData>, Error> =
@@ -354,17 +502,14 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
     }
 
     #[tokio::test]
     async fn test_get_virtual_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountGetRequest::new();
         params.tracking_reference = "0012".to_string();
@@ -379,18 +524,15 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
     }
 
     #[tokio::test]
     async fn test_get_virtual_account_balance() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountGetRequest::new();
         params.tracking_reference = "0012".to_string();
@@ -405,18 +547,15 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Operation successful");
     }
 
     #[tokio::test]
     async fn test_get_virtual_account_transaction_history() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountTransactionHistoryRequest::new();
         params.tracking_reference = "0012".to_string();
@@ -433,23 +572,20 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
     }
 
     #[tokio::test]
     async fn test_get_virtual_account_filtered_transaction_history() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountFilteredTransactionHistoryRequest::new();
         params.tracking_reference = "0012".to_string();
-        params.start_date = "2021-05-24T00:00:00".to_string();
-        params.end_date = "2021-05-24T23:59:59".to_string();
+        let day = NaiveDate::from_ymd_opt(2021, 5, 24).unwrap();
+        params.set_dates(day..=day);
         params.page_number = 1;
         params.page_size = 100;
 
@@ -463,22 +599,50 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Request successful.");
+        assert!(account.data.unwrap().postings_history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_transaction_history_dates() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::ZERO);
+
+        let mut fund = VirtualAccountFundWithdrawRequest::new();
+        fund.tracking_reference = "0012".to_string();
+        fund.amount = Money::from_naira(100);
+        fund.narration = "test fund".to_string();
+        VirtualAccount::fund_virtual_account(&client, fund)
+            .await
+            .unwrap();
+
+        let today = chrono::Utc::now().date_naive();
+        let mut params = VirtualAccountFilteredTransactionHistoryRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.set_dates(today.pred_opt().unwrap()..=today);
+        params.page_number = 1;
+        params.page_size = 100;
+
+        let response =
+            VirtualAccount::get_virtual_account_filtered_transaction_history(&client, params)
+                .await
+                .unwrap();
+        let postings = response.data.unwrap().postings_history;
+        assert_eq!(postings.len(), 1);
+        assert_eq!(postings[0].real_date.date(), today);
     }
 
     #[tokio::test]
     async fn test_fund_virtual_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("6b381fdf-933c-4c33-8fcf-6a6c3372fbc4", Money::ZERO);
 
         let mut params = VirtualAccountFundWithdrawRequest::new();
         params.tracking_reference = "6b381fdf-933c-4c33-8fcf-6a6c3372fbc4".to_string();
-        params.amount = "1777860".to_string();
+        params.amount = Money::from_kobo(1777860);
         params.narration = "REVERT".to_string();
 
         let response: Result<R<serde_json::Value>, Error> =
@@ -491,22 +655,19 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Transaction successful");
     }
 
     #[tokio::test]
     async fn test_withdraw_virtual_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountFundWithdrawRequest::new();
         params.tracking_reference = "0012".to_string();
-        params.amount = "1000".to_string();
+        params.amount = Money::from_kobo(1000);
         params.narration = "test withdraw".to_string();
 
         let response: Result<R<serde_json::Value>, Error> =
@@ -519,30 +680,27 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Transaction successful");
     }
 
     #[tokio::test]
     async fn test_fund_transfer_virtual_account() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
 
         let mut params = VirtualAccountFundTransferRequest::new();
         params.tracking_reference = "0012".to_string();
         params.beneficiary_account = "0012".to_string();
-        params.amount = "1000".to_string();
+        params.amount = Money::from_kobo(1000);
         params.narration = "test fund transfer".to_string();
         params.beneficiary_bank_code = "0012".to_string();
         params.beneficiary_name = "test beneficiary".to_string();
         params.sender_name = "test sender".to_string();
         params.name_enquiry_id = "0".to_string();
 
-        let response: Result<R<serde_json::Value>, Error> =
+        let response: Result<R<FundTransferResponseData>, Error> =
             VirtualAccount::fund_transfer_virtual_account(&client, params).await;
         let account = match response {
             Err(err) => panic!("{:?}", err),
@@ -552,7 +710,98 @@ mod tests {
             }
         };
 
-        assert_eq!(account.status, true);
+        assert!(account.status);
         assert_eq!(account.message, "Transaction successful.");
     }
+
+    #[tokio::test]
+    async fn test_update_virtual_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
+
+        let mut params = VirtualAccountUpdateRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.first_name = "Mark".to_string();
+        params.last_name = "Smith".to_string();
+        params.email = "example@email.com".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            VirtualAccount::update_virtual_account(&client, params).await;
+        let account = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(account.status);
+    }
+
+    #[tokio::test]
+    async fn test_disable_virtual_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
+
+        let mut params = VirtualAccountGetRequest::new();
+        params.tracking_reference = "0012".to_string();
+
+        let response: Result<R<VirtualAccountStatusResponseData>, Error> =
+            VirtualAccount::disable_virtual_account(&client, params).await;
+        let account = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(account.status);
+    }
+
+    #[tokio::test]
+    async fn test_enable_virtual_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.create_virtual_account("0012", Money::from_naira(1000));
+
+        let mut params = VirtualAccountGetRequest::new();
+        params.tracking_reference = "0012".to_string();
+
+        let response: Result<R<VirtualAccountStatusResponseData>, Error> =
+            VirtualAccount::enable_virtual_account(&client, params).await;
+        let account = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(account.status);
+    }
+
+    #[tokio::test]
+    async fn test_list_virtual_accounts() {
+        let server = MockServer::start().await;
+        let client = server.client();
+
+        let mut params = VirtualAccountListRequest::new();
+        params.page_number = 1;
+        params.page_size = 100;
+
+        let response: Result<R<VirtualAccountListResponseData>, Error> =
+            VirtualAccount::list_virtual_accounts(&client, params).await;
+        let account = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(account.status);
+    }
 }
@@ -1,6 +1,6 @@
 use serde_derive::{Deserialize, Serialize};
 
-use crate::config::{Client, Response};
+use crate::client::transport::KudaTransport;
 use crate::params::Response as R;
 
 pub struct Bank {}
@@ -20,7 +20,7 @@ pub struct GetBankListBankResponseBank {
 
 impl Bank {
     // Get Bank List.
-    pub fn get_bank_list(client: &Client) -> Response<R<GetBankListResponseData>> {
+    pub fn get_bank_list<C: KudaTransport>(client: &C) -> C::Response<R<GetBankListResponseData>> {
         client.post_body("/v1", "BANK_LIST", None::<Box<String>>)
     }
 }
@@ -28,20 +28,13 @@ impl Bank {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockServer;
     use crate::Error;
 
-    const PUBLIC_KEY: &str = "CHANGE_ME";
-
-    const PRIVATE_KEY: &str = "CHANGE_ME";
-
     #[tokio::test]
     async fn test_get_bank_list() {
-        let client = Client::new(
-            "https://kuda-openapi-uat.kudabank.com".to_string(),
-            PRIVATE_KEY.to_string(),
-            PUBLIC_KEY.to_string(),
-            "CHANGE_ME".to_string(),
-        );
+        let server = MockServer::start().await;
+        let client = server.client();
 
         let response: Result<R<GetBankListResponseData>, Error> =
             Bank::get_bank_list(&client).await;
@@ -53,7 +46,7 @@ mod tests {
             }
         };
 
-        assert_eq!(bank.status, false);
+        assert!(!bank.status);
         assert_eq!(bank.message, "Completed Successfully");
     }
 }
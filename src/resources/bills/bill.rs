@@ -0,0 +1,472 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::client::transport::KudaTransport;
+use crate::money::Money;
+use crate::params::Response as R;
+
+pub struct Bills {}
+
+/// A category of billers.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BillType {
+    #[default]
+    #[serde(rename = "airtime")]
+    Airtime,
+    #[serde(rename = "betting")]
+    Betting,
+    #[serde(rename = "internet")]
+    Internet,
+    #[serde(rename = "electricity")]
+    Electricity,
+    #[serde(rename = "cableTv")]
+    CableTv,
+}
+
+/// The parameters for `Bills::request::billers`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillersRequest {
+    /// The category of billers to list.
+    pub bill_type_name: BillType,
+}
+
+impl BillersRequest {
+    pub fn new() -> Self {
+        BillersRequest {
+            bill_type_name: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillersResponseData {
+    pub billers: Vec<Biller>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Biller {
+    pub biller_name: String,
+    /// Identifier of the bill item, used to verify customers and purchase.
+    pub biller_item_identifier: String,
+    pub biller_identifier: Option<String>,
+    /// Fixed price of the bill item, if any. All amounts in kobo.
+    #[serde(with = "crate::money::option", default)]
+    pub amount: Option<Money>,
+    #[serde(with = "crate::money::option", default)]
+    pub commission: Option<Money>,
+}
+
+/// The parameters for `Bills::request::verify_customer`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillCustomerVerifyRequest {
+    /// Identifier of the bill item.
+    pub kuda_bill_item_identifier: String,
+    /// Customer's meter number, smartcard number or phone number.
+    pub customer_identification: String,
+}
+
+impl BillCustomerVerifyRequest {
+    pub fn new() -> Self {
+        BillCustomerVerifyRequest {
+            kuda_bill_item_identifier: Default::default(),
+            customer_identification: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillCustomerVerifyResponseData {
+    pub customer_name: String,
+}
+
+/// The parameters for `Bills::request::purchase`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdminBillPurchaseRequest {
+    /// Amount to be paid. All amounts in kobo.
+    pub amount: Money,
+    /// Identifier of the bill item.
+    pub bill_item_identifier: String,
+    /// Phone number of the customer.
+    pub phone_number: String,
+    /// Customer's meter number, smartcard number or phone number.
+    pub customer_identifier: String,
+}
+
+impl AdminBillPurchaseRequest {
+    pub fn new() -> Self {
+        AdminBillPurchaseRequest {
+            amount: Default::default(),
+            bill_item_identifier: Default::default(),
+            phone_number: Default::default(),
+            customer_identifier: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `Bills::request::purchase_virtual_account`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct VirtualAccountBillPurchaseRequest {
+    /// Unique identifier of the paying virtual account.
+    pub tracking_reference: String,
+    /// Amount to be paid. All amounts in kobo.
+    pub amount: Money,
+    /// Identifier of the bill item.
+    pub bill_item_identifier: String,
+    /// Phone number of the customer.
+    pub phone_number: String,
+    /// Customer's meter number, smartcard number or phone number.
+    pub customer_identifier: String,
+}
+
+impl VirtualAccountBillPurchaseRequest {
+    pub fn new() -> Self {
+        VirtualAccountBillPurchaseRequest {
+            tracking_reference: Default::default(),
+            amount: Default::default(),
+            bill_item_identifier: Default::default(),
+            phone_number: Default::default(),
+            customer_identifier: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillPurchaseResponseData {
+    /// Reference used to query the status of the purchase.
+    pub reference: String,
+}
+
+/// The parameters for `Bills::request::purchased_bills`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchasedBillsRequest {
+    pub page_size: i64,
+    pub page_number: i64,
+}
+
+impl PurchasedBillsRequest {
+    pub fn new() -> Self {
+        PurchasedBillsRequest {
+            page_size: Default::default(),
+            page_number: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchasedBillsResponseData {
+    pub bills: Vec<PurchasedBill>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchasedBill {
+    pub reference: String,
+    pub bill_item_identifier: Option<String>,
+    pub biller_name: Option<String>,
+    pub customer_identifier: Option<String>,
+    #[serde(with = "crate::money::number")]
+    pub amount: Money,
+    pub status: Option<String>,
+    pub transaction_date: Option<String>,
+}
+
+/// The parameters for `Bills::request::status`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillStatusRequest {
+    /// Reference returned by the purchase request.
+    pub bill_response_reference: String,
+}
+
+impl BillStatusRequest {
+    pub fn new() -> Self {
+        BillStatusRequest {
+            bill_response_reference: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BillStatusResponseData {
+    pub has_been_reversed: bool,
+    pub status: Option<String>,
+    /// Token or PIN issued for electricity and similar purchases.
+    pub token: Option<String>,
+}
+
+impl Bills {
+    /// Get Billers By Type.
+    pub fn get_billers_by_type<C: KudaTransport>(
+        client: &C,
+        params: BillersRequest,
+    ) -> C::Response<R<BillersResponseData>> {
+        client.post_body("/v1", "GET_BILLERS_BY_TYPE", params)
+    }
+
+    /// Verify Bill Customer.
+    pub fn verify_bill_customer<C: KudaTransport>(
+        client: &C,
+        params: BillCustomerVerifyRequest,
+    ) -> C::Response<R<BillCustomerVerifyResponseData>> {
+        client.post_body("/v1", "VERIFY_BILL_CUSTOMER", params)
+    }
+
+    /// Purchase a bill from the main account.
+    pub fn purchase_bill<C: KudaTransport>(
+        client: &C,
+        params: AdminBillPurchaseRequest,
+    ) -> C::Response<R<BillPurchaseResponseData>> {
+        client.post_body("/v1", "ADMIN_PURCHASE_BILL", params)
+    }
+
+    /// Purchase a bill from a virtual account.
+    pub fn purchase_bill_virtual_account<C: KudaTransport>(
+        client: &C,
+        params: VirtualAccountBillPurchaseRequest,
+    ) -> C::Response<R<BillPurchaseResponseData>> {
+        client.post_body("/v1", "VIRTUAL_ACCOUNT_PURCHASE_BILL", params)
+    }
+
+    /// Retrieve a page of bills purchased from the main account.
+    pub fn get_purchased_bills<C: KudaTransport>(
+        client: &C,
+        params: PurchasedBillsRequest,
+    ) -> C::Response<R<PurchasedBillsResponseData>> {
+        client.post_body("/v1", "ADMIN_GET_PURCHASED_BILLS", params)
+    }
+
+    /// Check the status of a bill purchase.
+    pub fn get_bill_status<C: KudaTransport>(
+        client: &C,
+        params: BillStatusRequest,
+    ) -> C::Response<R<BillStatusResponseData>> {
+        client.post_body("/v1", "BILL_TSQ", params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::Error;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_get_billers_by_type() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("GET_BILLERS_BY_TYPE", BillersResponseData::default());
+
+        let mut params = BillersRequest::new();
+        params.bill_type_name = BillType::Airtime;
+
+        let response: Result<R<BillersResponseData>, Error> =
+            Bills::get_billers_by_type(&client, params).await;
+        let billers = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(billers.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "GET_BILLERS_BY_TYPE");
+        assert_eq!(requests[0].data, Some(json!({ "BillTypeName": "airtime" })));
+    }
+
+    #[tokio::test]
+    async fn test_verify_bill_customer() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed(
+            "VERIFY_BILL_CUSTOMER",
+            BillCustomerVerifyResponseData::default(),
+        );
+
+        let mut params = BillCustomerVerifyRequest::new();
+        params.kuda_bill_item_identifier = "KUD-ELE-IKEDC-PREPAID".to_string();
+        params.customer_identification = "45030000000".to_string();
+
+        let response: Result<R<BillCustomerVerifyResponseData>, Error> =
+            Bills::verify_bill_customer(&client, params).await;
+        let customer = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(customer.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "VERIFY_BILL_CUSTOMER");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "KudaBillItemIdentifier": "KUD-ELE-IKEDC-PREPAID",
+                "CustomerIdentification": "45030000000"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purchase_bill() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("ADMIN_PURCHASE_BILL", BillPurchaseResponseData::default());
+
+        let mut params = AdminBillPurchaseRequest::new();
+        params.amount = Money::from_kobo(10000);
+        params.bill_item_identifier = "KUD-AIR-MTN".to_string();
+        params.phone_number = "09039658058".to_string();
+        params.customer_identifier = "09039658058".to_string();
+
+        let response: Result<R<BillPurchaseResponseData>, Error> =
+            Bills::purchase_bill(&client, params).await;
+        let bill = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(bill.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "ADMIN_PURCHASE_BILL");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "Amount": "10000",
+                "BillItemIdentifier": "KUD-AIR-MTN",
+                "PhoneNumber": "09039658058",
+                "CustomerIdentifier": "09039658058"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purchase_bill_virtual_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed(
+            "VIRTUAL_ACCOUNT_PURCHASE_BILL",
+            BillPurchaseResponseData::default(),
+        );
+
+        let mut params = VirtualAccountBillPurchaseRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.amount = Money::from_kobo(10000);
+        params.bill_item_identifier = "KUD-AIR-MTN".to_string();
+        params.phone_number = "09039658058".to_string();
+        params.customer_identifier = "09039658058".to_string();
+
+        let response: Result<R<BillPurchaseResponseData>, Error> =
+            Bills::purchase_bill_virtual_account(&client, params).await;
+        let bill = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(bill.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "VIRTUAL_ACCOUNT_PURCHASE_BILL");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "TrackingReference": "0012",
+                "Amount": "10000",
+                "BillItemIdentifier": "KUD-AIR-MTN",
+                "PhoneNumber": "09039658058",
+                "CustomerIdentifier": "09039658058"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_purchased_bills() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed(
+            "ADMIN_GET_PURCHASED_BILLS",
+            PurchasedBillsResponseData::default(),
+        );
+
+        let mut params = PurchasedBillsRequest::new();
+        params.page_number = 1;
+        params.page_size = 100;
+
+        let response: Result<R<PurchasedBillsResponseData>, Error> =
+            Bills::get_purchased_bills(&client, params).await;
+        let bills = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(bills.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "ADMIN_GET_PURCHASED_BILLS");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "PageSize": 100, "PageNumber": 1 }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_bill_status() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("BILL_TSQ", BillStatusResponseData::default());
+
+        let mut params = BillStatusRequest::new();
+        params.bill_response_reference = "SP-SBI8IIU".to_string();
+
+        let response: Result<R<BillStatusResponseData>, Error> =
+            Bills::get_bill_status(&client, params).await;
+        let bill = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(bill.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "BILL_TSQ");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "BillResponseReference": "SP-SBI8IIU" }))
+        );
+    }
+}
@@ -0,0 +1,565 @@
+use std::fmt;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::client::transport::KudaTransport;
+use crate::money::Money;
+use crate::params::Response as R;
+
+pub struct Cards {}
+
+/// The state of a card.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardStatus {
+    /// The card has been requested but not yet delivered.
+    #[default]
+    Pending,
+    /// The card has been delivered but not activated.
+    Inactive,
+    Active,
+    Blocked,
+    Expired,
+    /// A status not (yet?) known to this library.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A channel a card can be used on.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardChannel {
+    #[default]
+    #[serde(rename = "ATM")]
+    Atm,
+    #[serde(rename = "POS")]
+    Pos,
+    #[serde(rename = "WEB")]
+    Web,
+}
+
+/// The parameters for `Cards::request_card`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+    /// Name printed on the card.
+    pub name_on_card: String,
+    /// Address the card is delivered to.
+    pub delivery_address: String,
+}
+
+impl CardRequest {
+    pub fn new() -> Self {
+        CardRequest {
+            tracking_reference: Default::default(),
+            name_on_card: Default::default(),
+            delivery_address: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardRequestResponseData {
+    #[serde(rename = "CardId")]
+    pub card_id: String,
+}
+
+/// The parameters for `Cards::get_cards`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardGetRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+}
+
+impl CardGetRequest {
+    pub fn new() -> Self {
+        CardGetRequest {
+            tracking_reference: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardGetResponseData {
+    #[serde(rename = "Cards")]
+    pub cards: Vec<CardGetResponseCard>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardGetResponseCard {
+    #[serde(rename = "CardId")]
+    pub card_id: String,
+    #[serde(rename = "MaskedPan")]
+    pub masked_pan: String,
+    #[serde(rename = "NameOnCard")]
+    pub name_on_card: String,
+    #[serde(rename = "ExpiryDate")]
+    pub expiry_date: String,
+    #[serde(rename = "Status")]
+    pub status: CardStatus,
+}
+
+/// The parameters for `Cards::activate_card`, `Cards::block_card` and
+/// `Cards::unblock_card`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardActionRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+    /// Identifier of the card.
+    pub card_id: String,
+}
+
+impl CardActionRequest {
+    pub fn new() -> Self {
+        CardActionRequest {
+            tracking_reference: Default::default(),
+            card_id: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `Cards::set_card_pin`.
+///
+/// The PIN is left out of its `Debug` output so it does not end up in logs.
+#[derive(Default, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardPinRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+    /// Identifier of the card.
+    pub card_id: String,
+    /// The new four digit PIN.
+    pub pin: String,
+}
+
+impl CardPinRequest {
+    pub fn new() -> Self {
+        CardPinRequest {
+            tracking_reference: Default::default(),
+            card_id: Default::default(),
+            pin: Default::default(),
+        }
+    }
+}
+
+impl fmt::Debug for CardPinRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardPinRequest")
+            .field("tracking_reference", &self.tracking_reference)
+            .field("card_id", &self.card_id)
+            .field("pin", &"<redacted>")
+            .finish()
+    }
+}
+
+/// The parameters for `Cards::manage_card_channel`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardChannelRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+    /// Identifier of the card.
+    pub card_id: String,
+    pub channel: CardChannel,
+    /// Whether the card may be used on the channel.
+    pub enabled: bool,
+}
+
+impl CardChannelRequest {
+    pub fn new() -> Self {
+        CardChannelRequest {
+            tracking_reference: Default::default(),
+            card_id: Default::default(),
+            channel: Default::default(),
+            enabled: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `Cards::set_card_limit`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardLimitRequest {
+    /// Unique identifier of the card holder's account.
+    pub tracking_reference: String,
+    /// Identifier of the card.
+    pub card_id: String,
+    pub channel: CardChannel,
+    /// Maximum spend per day on the channel. All amounts in kobo.
+    pub limit: Money,
+}
+
+impl CardLimitRequest {
+    pub fn new() -> Self {
+        CardLimitRequest {
+            tracking_reference: Default::default(),
+            card_id: Default::default(),
+            channel: Default::default(),
+            limit: Default::default(),
+        }
+    }
+}
+
+impl Cards {
+    /// Request a debit card for an account.
+    pub fn request_card<C: KudaTransport>(
+        client: &C,
+        params: CardRequest,
+    ) -> C::Response<R<CardRequestResponseData>> {
+        client.post_body("/v1", "REQUEST_CARD", params)
+    }
+
+    /// Get the cards issued to an account.
+    pub fn get_cards<C: KudaTransport>(
+        client: &C,
+        params: CardGetRequest,
+    ) -> C::Response<R<CardGetResponseData>> {
+        client.post_body("/v1", "GET_CUSTOMER_CARDS", params)
+    }
+
+    /// Activate Card.
+    pub fn activate_card<C: KudaTransport>(
+        client: &C,
+        params: CardActionRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "ACTIVATE_CARD", params)
+    }
+
+    /// Block Card.
+    pub fn block_card<C: KudaTransport>(
+        client: &C,
+        params: CardActionRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "BLOCK_CARD", params)
+    }
+
+    /// Unblock Card.
+    pub fn unblock_card<C: KudaTransport>(
+        client: &C,
+        params: CardActionRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "UNBLOCK_CARD", params)
+    }
+
+    /// Set Card PIN.
+    pub fn set_card_pin<C: KudaTransport>(
+        client: &C,
+        params: CardPinRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "SET_CARD_PIN", params)
+    }
+
+    /// Enable or disable a card on a channel.
+    pub fn manage_card_channel<C: KudaTransport>(
+        client: &C,
+        params: CardChannelRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "MANAGE_CARD_CHANNEL", params)
+    }
+
+    /// Set the daily spending limit of a card on a channel.
+    pub fn set_card_limit<C: KudaTransport>(
+        client: &C,
+        params: CardLimitRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        client.post_body("/v1", "SET_CARD_LIMIT", params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::Error;
+    use serde_json::json;
+
+    #[test]
+    fn test_card_status() {
+        let card: CardGetResponseCard = serde_json::from_str(
+            r#"{"CardId":"1","MaskedPan":"506099******1234","NameOnCard":"MARK SMITH","ExpiryDate":"05/24","Status":"Hotlisted"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(card.status, CardStatus::Unknown);
+    }
+
+    #[test]
+    fn test_card_pin_redacted() {
+        let mut params = CardPinRequest::new();
+        params.card_id = "1".to_string();
+        params.pin = "1234".to_string();
+
+        let debug = format!("{:?}", params);
+        assert!(debug.contains("card_id: \"1\""));
+        assert!(!debug.contains("1234"));
+    }
+
+    #[tokio::test]
+    async fn test_request_card() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("REQUEST_CARD", CardRequestResponseData::default());
+
+        let mut params = CardRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.name_on_card = "Mark Smith".to_string();
+        params.delivery_address = "1 Example Street, Lagos".to_string();
+
+        let response: Result<R<CardRequestResponseData>, Error> =
+            Cards::request_card(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "REQUEST_CARD");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012",
+                "nameOnCard": "Mark Smith",
+                "deliveryAddress": "1 Example Street, Lagos"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_cards() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("GET_CUSTOMER_CARDS", CardGetResponseData::default());
+
+        let mut params = CardGetRequest::new();
+        params.tracking_reference = "0012".to_string();
+
+        let response: Result<R<CardGetResponseData>, Error> =
+            Cards::get_cards(&client, params).await;
+        let cards = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(cards.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "GET_CUSTOMER_CARDS");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_activate_card() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("ACTIVATE_CARD", serde_json::Value::Null);
+
+        let mut params = CardActionRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::activate_card(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "ACTIVATE_CARD");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012", "cardId": "0012" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_block_card() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("BLOCK_CARD", serde_json::Value::Null);
+
+        let mut params = CardActionRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::block_card(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "BLOCK_CARD");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012", "cardId": "0012" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unblock_card() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("UNBLOCK_CARD", serde_json::Value::Null);
+
+        let mut params = CardActionRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::unblock_card(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "UNBLOCK_CARD");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012", "cardId": "0012" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_card_pin() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("SET_CARD_PIN", serde_json::Value::Null);
+
+        let mut params = CardPinRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+        params.pin = "1234".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::set_card_pin(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "SET_CARD_PIN");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012", "cardId": "0012", "pin": "1234" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manage_card_channel() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("MANAGE_CARD_CHANNEL", serde_json::Value::Null);
+
+        let mut params = CardChannelRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+        params.channel = CardChannel::Pos;
+        params.enabled = false;
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::manage_card_channel(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "MANAGE_CARD_CHANNEL");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012",
+                "cardId": "0012",
+                "channel": "POS",
+                "enabled": false
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_card_limit() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("SET_CARD_LIMIT", serde_json::Value::Null);
+
+        let mut params = CardLimitRequest::new();
+        params.tracking_reference = "0012".to_string();
+        params.card_id = "0012".to_string();
+        params.channel = CardChannel::Web;
+        params.limit = Money::from_kobo(5000000);
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Cards::set_card_limit(&client, params).await;
+        let card = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(card.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "SET_CARD_LIMIT");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012",
+                "cardId": "0012",
+                "channel": "WEB",
+                "limit": "5000000"
+            }))
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use futures::stream;
+
+use crate::config::{Response, ResponseStream};
+use crate::error::RequestError;
+use crate::params::Response as R;
+
+/// Page size used when a request leaves `page_size` unset.
+const DEFAULT_PAGE_SIZE: i64 = 100;
+
+/// A page of results that knows how many records exist in total.
+pub(crate) trait Page {
+    type Item;
+
+    /// Splits the page into its records and the total record count.
+    fn into_records(self) -> (Vec<Self::Item>, i64);
+}
+
+struct Pager<F, T> {
+    fetch: F,
+    page_number: i64,
+    page_size: i64,
+    records: VecDeque<T>,
+    seen: i64,
+    done: bool,
+}
+
+/// Lazily fetches pages with `fetch(page_number, page_size)` and yields their
+/// records one at a time until the total record count has been reached.
+///
+/// The stream ends after the first error.
+pub(crate) fn paginate<D, F>(page_number: i64, page_size: i64, fetch: F) -> ResponseStream<D::Item>
+where
+    D: Page + Send + 'static,
+    D::Item: Send + 'static,
+    F: FnMut(i64, i64) -> Response<R<D>> + Send + 'static,
+{
+    let pager = Pager {
+        fetch,
+        page_number: page_number.max(1),
+        page_size: if page_size > 0 {
+            page_size
+        } else {
+            DEFAULT_PAGE_SIZE
+        },
+        records: VecDeque::new(),
+        seen: 0,
+        done: false,
+    };
+
+    Box::pin(stream::unfold(pager, |mut pager| async move {
+        loop {
+            if let Some(record) = pager.records.pop_front() {
+                return Some((Ok(record), pager));
+            }
+            if pager.done {
+                return None;
+            }
+
+            let page = match (pager.fetch)(pager.page_number, pager.page_size).await {
+                Err(err) => {
+                    pager.done = true;
+                    return Some((Err(err), pager));
+                }
+                Ok(page) => page,
+            };
+            let data = match page.data {
+                Some(data) if page.status => data,
+                _ if page.status => {
+                    pager.done = true;
+                    continue;
+                }
+                _ => {
+                    pager.done = true;
+                    return Some((Err(RequestError::rejected(&page).into()), pager));
+                }
+            };
+
+            let (records, total) = data.into_records();
+            let count = records.len() as i64;
+            pager.seen += count;
+            pager.page_number += 1;
+            // A short page does not mean the last one, as Kuda may cap the
+            // page size; only the total record count does.
+            pager.done = count == 0 || pager.seen >= total;
+            pager.records.extend(records);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::r#async::ok;
+    use futures::StreamExt;
+
+    #[derive(Default)]
+    struct TestPage(Vec<i64>, i64);
+
+    impl Page for TestPage {
+        type Item = i64;
+
+        fn into_records(self) -> (Vec<i64>, i64) {
+            (self.0, self.1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginate() {
+        let records = paginate(1, 2, |page_number, page_size| {
+            let start = (page_number - 1) * page_size;
+            let records = (start..(start + page_size).min(5)).collect();
+            ok(R {
+                status: true,
+                data: Some(TestPage(records, 5)),
+                ..Default::default()
+            })
+        });
+
+        let records: Vec<i64> = records.map(Result::unwrap).collect().await;
+        assert_eq!(records, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_capped_page_size() {
+        let records = paginate(1, 5, |page_number, _| {
+            let page_size = 2;
+            let start = (page_number - 1) * page_size;
+            let records = (start..(start + page_size).min(5)).collect();
+            ok(R {
+                status: true,
+                data: Some(TestPage(records, 5)),
+                ..Default::default()
+            })
+        });
+
+        let records: Vec<i64> = records.map(Result::unwrap).collect().await;
+        assert_eq!(records, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn test_paginate_rejected() {
+        let mut records = paginate(1, 2, |_, _| {
+            ok(R::<TestPage> {
+                status: false,
+                message: "Invalid tracking reference".to_string(),
+                ..Default::default()
+            })
+        });
+
+        match records.next().await {
+            Some(Err(crate::Error::Kuda(err))) => {
+                assert_eq!(err.message, "Invalid tracking reference")
+            }
+            other => panic!("{:?}", other.map(|record| record.map(|_| ()))),
+        }
+        assert!(records.next().await.is_none());
+    }
+}
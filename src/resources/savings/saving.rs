@@ -0,0 +1,598 @@
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::client::transport::KudaTransport;
+use crate::error::Error;
+use crate::money::Money;
+use crate::params::Response as R;
+use crate::resources::{
+    VirtualAccountTransactionHistoryRequest, VirtualAccountTransactionHistoryResponseData,
+};
+
+pub struct Savings {}
+
+/// A savings product offered by Kuda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavingsProduct {
+    /// Savings that can be funded and withdrawn at any time.
+    Plain,
+    /// Savings funded automatically on a schedule.
+    OpenFlexible,
+    /// Savings locked until maturity.
+    Fixed,
+}
+
+/// An operation on a savings account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SavingsOperation {
+    Create,
+    Get,
+    Transactions,
+    Fund,
+    Withdraw,
+    Close,
+}
+
+impl SavingsProduct {
+    /// The Kuda service type for `operation` on this product, or
+    /// `Error::Unsupported` if Kuda offers no such operation.
+    #[allow(clippy::result_large_err)]
+    fn service_type(self, operation: SavingsOperation) -> Result<&'static str, Error> {
+        use SavingsOperation::*;
+        use SavingsProduct::*;
+
+        match (self, operation) {
+            (Plain, Create) => Ok("CREATE_PLAIN_SAVINGS"),
+            (Plain, Get) => Ok("GET_PLAIN_SAVINGS"),
+            (Plain, Transactions) => Ok("PLAIN_SAVINGS_TRANSACTIONS"),
+            (Plain, Fund) | (Plain, Withdraw) => Ok("CREDIT_DEBIT_PLAIN_SAVINGS"),
+            (Plain, Close) => Err(Error::Unsupported(
+                "plain savings are emptied by withdrawing from them",
+            )),
+            (OpenFlexible, Create) => Ok("CREATE_OPEN_FLEXIBLE_SAVE"),
+            (OpenFlexible, Get) => Ok("GET_OPEN_FLEXIBLE_SAVE"),
+            (OpenFlexible, Transactions) => Ok("OPEN_FLEXIBLE_SAVE_TRANSACTIONS"),
+            (OpenFlexible, Withdraw) => Ok("COMPLETE_OPEN_FLEXIBLE_SAVE_WITHDRAWAL"),
+            (OpenFlexible, Fund) => Err(Error::Unsupported(
+                "open flexible savings are funded on their schedule",
+            )),
+            (OpenFlexible, Close) => Err(Error::Unsupported(
+                "open flexible savings are emptied by withdrawing from them",
+            )),
+            (Fixed, Create) => Ok("CREATE_FIXED_SAVE"),
+            (Fixed, Get) => Ok("GET_FIXED_SAVE"),
+            (Fixed, Transactions) => Ok("FIXED_SAVE_TRANSACTIONS"),
+            (Fixed, Close) => Ok("CLOSE_FIXED_SAVE"),
+            (Fixed, Fund) => Err(Error::Unsupported(
+                "fixed savings can only be funded on creation",
+            )),
+            (Fixed, Withdraw) => Err(Error::Unsupported(
+                "fixed savings can only be withdrawn by closing them",
+            )),
+        }
+    }
+}
+
+/// How often an open flexible savings account is funded.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavingsFrequency {
+    #[default]
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// The parameters for `Savings::request::create_plain`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainSavingsCreateRequest {
+    /// Unique identifier for the savings account.
+    pub tracking_reference: String,
+    /// Unique identifier of the virtual account that owns the savings.
+    pub virtual_account_tracking_reference: String,
+    /// Name of the savings goal.
+    pub name: String,
+}
+
+impl PlainSavingsCreateRequest {
+    pub fn new() -> Self {
+        PlainSavingsCreateRequest {
+            tracking_reference: Default::default(),
+            virtual_account_tracking_reference: Default::default(),
+            name: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `Savings::request::create_open_flexible`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenFlexibleSavingsCreateRequest {
+    /// Unique identifier for the savings account.
+    pub tracking_reference: String,
+    /// Unique identifier of the virtual account that owns the savings.
+    pub virtual_account_tracking_reference: String,
+    /// Name of the savings goal.
+    pub name: String,
+    /// Amount saved at each interval. All amounts in kobo.
+    pub amount: Money,
+    /// How often the savings are funded.
+    pub frequency: SavingsFrequency,
+    /// Number of days the savings run for.
+    pub duration: i64,
+    /// Whether the first funding happens immediately.
+    pub start_now: bool,
+    /// Date of the first funding, when not starting now.
+    pub start_date: String,
+    pub is_interest_earning: bool,
+}
+
+impl OpenFlexibleSavingsCreateRequest {
+    pub fn new() -> Self {
+        OpenFlexibleSavingsCreateRequest {
+            tracking_reference: Default::default(),
+            virtual_account_tracking_reference: Default::default(),
+            name: Default::default(),
+            amount: Default::default(),
+            frequency: Default::default(),
+            duration: Default::default(),
+            start_now: Default::default(),
+            start_date: Default::default(),
+            is_interest_earning: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `Savings::request::create_fixed`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedSavingsCreateRequest {
+    /// Unique identifier for the savings account.
+    pub tracking_reference: String,
+    /// Unique identifier of the virtual account that owns the savings.
+    pub virtual_account_tracking_reference: String,
+    /// Name of the savings goal.
+    pub name: String,
+    /// Amount locked for the duration. All amounts in kobo.
+    pub amount: Money,
+    /// Number of days the savings are locked for.
+    pub duration: i64,
+    pub start_now: bool,
+    pub start_date: String,
+    pub is_interest_earning: bool,
+}
+
+impl FixedSavingsCreateRequest {
+    pub fn new() -> Self {
+        FixedSavingsCreateRequest {
+            tracking_reference: Default::default(),
+            virtual_account_tracking_reference: Default::default(),
+            name: Default::default(),
+            amount: Default::default(),
+            duration: Default::default(),
+            start_now: Default::default(),
+            start_date: Default::default(),
+            is_interest_earning: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavingsCreateResponseData {
+    #[serde(rename = "AccountNumber")]
+    pub account_number: String,
+}
+
+/// The parameters for `Savings::request::get/close`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavingsGetRequest {
+    /// Unique identifier for the savings account.
+    pub tracking_reference: String,
+}
+
+impl SavingsGetRequest {
+    pub fn new() -> Self {
+        SavingsGetRequest {
+            tracking_reference: Default::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavingsGetResponseData {
+    #[serde(rename = "Account")]
+    pub account: SavingsGetResponseAccount,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavingsGetResponseAccount {
+    #[serde(rename = "AccountNumber")]
+    pub account_number: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "TrackingReference")]
+    pub tracking_reference: String,
+    #[serde(rename = "Balance", with = "crate::money::number")]
+    pub balance: Money,
+    #[serde(rename = "InterestRate")]
+    pub interest_rate: Option<f64>,
+    #[serde(rename = "Amount", with = "crate::money::option", default)]
+    pub amount: Option<Money>,
+    #[serde(rename = "Frequency")]
+    pub frequency: Option<String>,
+    #[serde(rename = "Duration")]
+    pub duration: Option<i64>,
+    #[serde(rename = "MaturityDate")]
+    pub maturity_date: Option<String>,
+    #[serde(rename = "Status")]
+    pub status: Option<String>,
+}
+
+/// The parameters for `Savings::request::fund/withdraw`.
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavingsFundWithdrawRequest {
+    /// Unique identifier for the savings account.
+    pub tracking_reference: String,
+    /// Amount to be funded/withdrawn. All amounts in kobo.
+    pub amount: Money,
+    /// Transaction narration.
+    pub narration: String,
+}
+
+impl SavingsFundWithdrawRequest {
+    pub fn new() -> Self {
+        SavingsFundWithdrawRequest {
+            tracking_reference: Default::default(),
+            amount: Default::default(),
+            narration: Default::default(),
+        }
+    }
+}
+
+/// Plain savings are funded and withdrawn through a single operation.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlainSavingsPostingRequest {
+    #[serde(flatten)]
+    params: SavingsFundWithdrawRequest,
+    transaction_type: &'static str,
+}
+
+/// The parameters for `Savings::request::transaction_history`.
+pub type SavingsTransactionHistoryRequest = VirtualAccountTransactionHistoryRequest;
+
+pub type SavingsTransactionHistoryResponseData = VirtualAccountTransactionHistoryResponseData;
+
+impl Savings {
+    /// Create Plain Savings Account.
+    pub fn create_plain_savings_account<C: KudaTransport>(
+        client: &C,
+        params: PlainSavingsCreateRequest,
+    ) -> C::Response<R<SavingsCreateResponseData>> {
+        Savings::post(
+            client,
+            SavingsProduct::Plain,
+            SavingsOperation::Create,
+            params,
+        )
+    }
+
+    /// Create Open Flexible Savings Account.
+    pub fn create_open_flexible_savings_account<C: KudaTransport>(
+        client: &C,
+        params: OpenFlexibleSavingsCreateRequest,
+    ) -> C::Response<R<SavingsCreateResponseData>> {
+        Savings::post(
+            client,
+            SavingsProduct::OpenFlexible,
+            SavingsOperation::Create,
+            params,
+        )
+    }
+
+    /// Create Fixed Savings Account.
+    pub fn create_fixed_savings_account<C: KudaTransport>(
+        client: &C,
+        params: FixedSavingsCreateRequest,
+    ) -> C::Response<R<SavingsCreateResponseData>> {
+        Savings::post(
+            client,
+            SavingsProduct::Fixed,
+            SavingsOperation::Create,
+            params,
+        )
+    }
+
+    /// Get Savings Account.
+    pub fn get_savings_account<C: KudaTransport>(
+        client: &C,
+        product: SavingsProduct,
+        params: SavingsGetRequest,
+    ) -> C::Response<R<SavingsGetResponseData>> {
+        Savings::post(client, product, SavingsOperation::Get, params)
+    }
+
+    /// Retrieve a list of all transactions for a savings account.
+    pub fn get_savings_transaction_history<C: KudaTransport>(
+        client: &C,
+        product: SavingsProduct,
+        params: SavingsTransactionHistoryRequest,
+    ) -> C::Response<R<SavingsTransactionHistoryResponseData>> {
+        Savings::post(client, product, SavingsOperation::Transactions, params)
+    }
+
+    /// Fund Savings Account.
+    ///
+    /// Only plain savings can be funded at will; open flexible savings are
+    /// funded on their schedule and fixed savings when they are created.
+    pub fn fund_savings_account<C: KudaTransport>(
+        client: &C,
+        product: SavingsProduct,
+        params: SavingsFundWithdrawRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        let params = PlainSavingsPostingRequest {
+            params,
+            transaction_type: "Credit",
+        };
+        Savings::post(client, product, SavingsOperation::Fund, params)
+    }
+
+    /// Withdraw Savings Account.
+    ///
+    /// Fixed savings are withdrawn by closing them.
+    pub fn withdraw_savings_account<C: KudaTransport>(
+        client: &C,
+        product: SavingsProduct,
+        params: SavingsFundWithdrawRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        match product {
+            SavingsProduct::Plain => {
+                let params = PlainSavingsPostingRequest {
+                    params,
+                    transaction_type: "Debit",
+                };
+                Savings::post(client, product, SavingsOperation::Withdraw, params)
+            }
+            _ => Savings::post(client, product, SavingsOperation::Withdraw, params),
+        }
+    }
+
+    /// Close Savings Account, returning its balance to the owning account.
+    ///
+    /// Only fixed savings are closed; the others are emptied by withdrawing.
+    pub fn close_savings_account<C: KudaTransport>(
+        client: &C,
+        product: SavingsProduct,
+        params: SavingsGetRequest,
+    ) -> C::Response<R<serde_json::Value>> {
+        Savings::post(client, product, SavingsOperation::Close, params)
+    }
+
+    fn post<C, T, B>(
+        client: &C,
+        product: SavingsProduct,
+        operation: SavingsOperation,
+        params: B,
+    ) -> C::Response<R<T>>
+    where
+        C: KudaTransport,
+        T: DeserializeOwned + Send + 'static,
+        B: serde::Serialize,
+    {
+        match product.service_type(operation) {
+            Ok(service_type) => client.post_body("/v1", service_type, params),
+            Err(error) => C::error(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use serde_json::json;
+
+    #[test]
+    fn test_savings_service_types() {
+        use SavingsOperation::*;
+
+        let service_type =
+            |product: SavingsProduct, operation| product.service_type(operation).unwrap();
+        assert_eq!(
+            service_type(SavingsProduct::Plain, Create),
+            "CREATE_PLAIN_SAVINGS"
+        );
+        assert_eq!(
+            service_type(SavingsProduct::Plain, Withdraw),
+            "CREDIT_DEBIT_PLAIN_SAVINGS"
+        );
+        assert_eq!(
+            service_type(SavingsProduct::OpenFlexible, Withdraw),
+            "COMPLETE_OPEN_FLEXIBLE_SAVE_WITHDRAWAL"
+        );
+        assert_eq!(
+            service_type(SavingsProduct::Fixed, Transactions),
+            "FIXED_SAVE_TRANSACTIONS"
+        );
+        assert_eq!(
+            service_type(SavingsProduct::Fixed, Close),
+            "CLOSE_FIXED_SAVE"
+        );
+
+        for (product, operation) in [
+            (SavingsProduct::Plain, Close),
+            (SavingsProduct::OpenFlexible, Fund),
+            (SavingsProduct::OpenFlexible, Close),
+            (SavingsProduct::Fixed, Fund),
+            (SavingsProduct::Fixed, Withdraw),
+        ] {
+            match product.service_type(operation) {
+                Err(Error::Unsupported(_)) => {}
+                other => panic!("{:?} {:?}: {:?}", product, operation, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_plain_savings_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("CREATE_PLAIN_SAVINGS", SavingsCreateResponseData::default());
+
+        let mut params = PlainSavingsCreateRequest::new();
+        params.tracking_reference = "0012-SAVINGS".to_string();
+        params.virtual_account_tracking_reference = "0012".to_string();
+        params.name = "Holiday".to_string();
+
+        let response: Result<R<SavingsCreateResponseData>, Error> =
+            Savings::create_plain_savings_account(&client, params).await;
+        let savings = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(savings.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "CREATE_PLAIN_SAVINGS");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012-SAVINGS",
+                "virtualAccountTrackingReference": "0012",
+                "name": "Holiday"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_savings_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("GET_PLAIN_SAVINGS", SavingsGetResponseData::default());
+
+        let mut params = SavingsGetRequest::new();
+        params.tracking_reference = "0012-SAVINGS".to_string();
+
+        let response: Result<R<SavingsGetResponseData>, Error> =
+            Savings::get_savings_account(&client, SavingsProduct::Plain, params).await;
+        let savings = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(savings.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "GET_PLAIN_SAVINGS");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({ "trackingReference": "0012-SAVINGS" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_savings_account() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed("CREDIT_DEBIT_PLAIN_SAVINGS", serde_json::Value::Null);
+
+        let mut params = SavingsFundWithdrawRequest::new();
+        params.tracking_reference = "0012-SAVINGS".to_string();
+        params.amount = Money::from_kobo(10000);
+        params.narration = "test savings".to_string();
+
+        let response: Result<R<serde_json::Value>, Error> =
+            Savings::fund_savings_account(&client, SavingsProduct::Plain, params).await;
+        let savings = match response {
+            Err(err) => panic!("{:?}", err),
+            Ok(ok) => {
+                println!("{:?}", ok);
+                ok
+            }
+        };
+
+        assert!(savings.status);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].service_type, "CREDIT_DEBIT_PLAIN_SAVINGS");
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012-SAVINGS",
+                "amount": "10000",
+                "narration": "test savings",
+                "transactionType": "Credit"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_savings_requests() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.succeed(
+            "COMPLETE_OPEN_FLEXIBLE_SAVE_WITHDRAWAL",
+            serde_json::Value::Null,
+        );
+        server.succeed("CLOSE_FIXED_SAVE", serde_json::Value::Null);
+
+        let mut params = SavingsFundWithdrawRequest::new();
+        params.tracking_reference = "0012-FLEX".to_string();
+        params.amount = Money::from_naira(50);
+        Savings::withdraw_savings_account(&client, SavingsProduct::OpenFlexible, params)
+            .await
+            .unwrap();
+
+        let mut params = SavingsGetRequest::new();
+        params.tracking_reference = "0012-FIXED".to_string();
+        Savings::close_savings_account(&client, SavingsProduct::Fixed, params)
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].service_type,
+            "COMPLETE_OPEN_FLEXIBLE_SAVE_WITHDRAWAL"
+        );
+        assert_eq!(
+            requests[0].data,
+            Some(json!({
+                "trackingReference": "0012-FLEX",
+                "amount": "5000",
+                "narration": ""
+            }))
+        );
+        assert_eq!(requests[1].service_type, "CLOSE_FIXED_SAVE");
+        assert_eq!(
+            requests[1].data,
+            Some(json!({ "trackingReference": "0012-FIXED" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_fixed_savings_account_unsupported() {
+        let server = MockServer::start().await;
+        let client = server.client();
+
+        let response =
+            Savings::fund_savings_account(&client, SavingsProduct::Fixed, Default::default()).await;
+        match response {
+            Err(Error::Unsupported(_)) => {}
+            other => panic!("{:?}", other),
+        }
+        assert!(server.requests().is_empty());
+    }
+}
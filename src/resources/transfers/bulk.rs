@@ -0,0 +1,358 @@
+use futures::stream::{self, StreamExt};
+
+use crate::config::{Client, Response};
+use crate::error::{Error, RequestError};
+use crate::money::Money;
+use crate::params::{Response as R, ResponseCode};
+use crate::resources::transfers::transfer::{self, enquire};
+use crate::resources::FundTransferResponseData;
+
+/// Number of transfers in flight when `BulkTransferRequest::concurrency` is unset.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+pub struct BulkTransfer {}
+
+/// The account a bulk transfer is paid from.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum TransferSource {
+    /// The main (admin) account.
+    #[default]
+    MainAccount,
+    /// A virtual account, identified by its tracking reference.
+    VirtualAccount(String),
+}
+
+/// A single payment in a bulk transfer.
+#[derive(Default, Clone, Debug)]
+pub struct BulkTransferItem {
+    /// Caller's reference for the payment, echoed in the report.
+    pub reference: String,
+    /// Destination bank account number.
+    pub beneficiary_account: String,
+    /// Destination bank code.
+    pub beneficiary_bank_code: String,
+    /// Amount to be transferred. All amounts in kobo.
+    pub amount: Money,
+    /// Transaction narration.
+    pub narration: String,
+}
+
+impl BulkTransferItem {
+    pub fn new() -> Self {
+        BulkTransferItem {
+            reference: Default::default(),
+            beneficiary_account: Default::default(),
+            beneficiary_bank_code: Default::default(),
+            amount: Default::default(),
+            narration: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `BulkTransfer::send`.
+#[derive(Default, Clone, Debug)]
+pub struct BulkTransferRequest {
+    /// The account every payment is made from.
+    pub source: TransferSource,
+    /// Name of the person sending money.
+    pub sender_name: String,
+    pub items: Vec<BulkTransferItem>,
+    /// Maximum number of payments in flight at once. Defaults to 8.
+    pub concurrency: usize,
+}
+
+impl BulkTransferRequest {
+    pub fn new() -> Self {
+        BulkTransferRequest {
+            source: Default::default(),
+            sender_name: Default::default(),
+            items: Default::default(),
+            concurrency: Default::default(),
+        }
+    }
+}
+
+/// What happened to a single payment.
+#[derive(Debug)]
+pub enum BulkTransferOutcome {
+    /// Kuda completed the transfer.
+    Success(R<FundTransferResponseData>),
+    /// Kuda accepted the transfer but has not completed it yet, or the
+    /// transfer was sent and its response lost (`Err`). Either way it may
+    /// still go through, so check its status by the result's
+    /// `request_reference` rather than sending it again.
+    Pending(Result<R<FundTransferResponseData>, Error>),
+    /// The name enquiry or transfer failed.
+    Failed(Error),
+}
+
+impl BulkTransferOutcome {
+    fn from_response(response: R<FundTransferResponseData>) -> BulkTransferOutcome {
+        if !response.status {
+            return BulkTransferOutcome::Failed(RequestError::rejected(&response).into());
+        }
+        match response.response_code {
+            Some(ResponseCode::Success) => BulkTransferOutcome::Success(response),
+            None | Some(_) => BulkTransferOutcome::Pending(Ok(response)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BulkTransferResult {
+    pub item: BulkTransferItem,
+    /// The `requestRef` the transfer was sent with, to check its status by.
+    /// `None` if the payment failed before the transfer was sent.
+    pub request_reference: Option<String>,
+    pub outcome: BulkTransferOutcome,
+}
+
+/// The result of every payment, in the order they were requested.
+#[derive(Debug, Default)]
+pub struct BulkTransferReport {
+    pub results: Vec<BulkTransferResult>,
+}
+
+impl BulkTransferReport {
+    pub fn succeeded(&self) -> impl Iterator<Item = &BulkTransferResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkTransferOutcome::Success(_)))
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &BulkTransferResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkTransferOutcome::Pending(_)))
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &BulkTransferResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkTransferOutcome::Failed(_)))
+    }
+}
+
+impl BulkTransfer {
+    /// Send Money to many beneficiaries.
+    ///
+    /// Each payment is confirmed with a name enquiry and then transferred,
+    /// with at most `concurrency` payments in flight. A failed payment does
+    /// not stop the others; every outcome is returned in the report.
+    ///
+    /// Async only: running payments concurrently needs the async [`Client`]
+    /// rather than any `KudaTransport`. With the blocking client, run it
+    /// through `blocking::Client::call`.
+    pub fn send(client: &Client, params: BulkTransferRequest) -> Response<BulkTransferReport> {
+        let client = client.clone();
+        Box::pin(async move {
+            let concurrency = match params.concurrency {
+                0 => DEFAULT_CONCURRENCY,
+                concurrency => concurrency,
+            };
+            let source = params.source;
+            let sender_name = params.sender_name;
+
+            let results = stream::iter(params.items)
+                .map(|item| {
+                    let client = client.clone();
+                    let source = source.clone();
+                    let sender_name = sender_name.clone();
+                    async move {
+                        let (request_reference, outcome) =
+                            transfer(&client, &source, &sender_name, &item).await;
+                        BulkTransferResult {
+                            item,
+                            request_reference,
+                            outcome,
+                        }
+                    }
+                })
+                .buffered(concurrency)
+                .collect()
+                .await;
+
+            Ok(BulkTransferReport { results })
+        })
+    }
+}
+
+/// Pays a single item, returning the `requestRef` of its transfer if one was
+/// sent.
+async fn transfer(
+    client: &Client,
+    source: &TransferSource,
+    sender_name: &str,
+    item: &BulkTransferItem,
+) -> (Option<String>, BulkTransferOutcome) {
+    let enquiry = match enquire(
+        client,
+        &item.beneficiary_account,
+        &item.beneficiary_bank_code,
+    )
+    .await
+    {
+        Ok(enquiry) => enquiry,
+        Err(err) => return (None, BulkTransferOutcome::Failed(err)),
+    };
+
+    let request_reference = client.request_ref();
+    let outcome = match transfer::transfer(
+        client,
+        source,
+        sender_name,
+        enquiry,
+        item.amount,
+        &item.narration,
+        request_reference.clone(),
+    )
+    .await
+    {
+        Ok(response) => BulkTransferOutcome::from_response(response),
+        // The transfer may have gone through even though its response was
+        // lost, so it must not be reported as failed and sent again.
+        Err(err) if transfer::was_sent(&err) => BulkTransferOutcome::Pending(Err(err)),
+        Err(err) => return (None, BulkTransferOutcome::Failed(err)),
+    };
+    (Some(request_reference), outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::mock::MockServer;
+
+    fn request(amounts: &[i64], concurrency: usize) -> BulkTransferRequest {
+        let mut params = BulkTransferRequest::new();
+        params.sender_name = "SeamPay".to_string();
+        params.concurrency = concurrency;
+        for (i, amount) in amounts.iter().enumerate() {
+            let mut item = BulkTransferItem::new();
+            item.reference = format!("payout-{}", i);
+            item.beneficiary_account = format!("01000000{:02}", i);
+            item.beneficiary_bank_code = "000013".to_string();
+            item.amount = Money::from_naira(*amount);
+            item.narration = "Payout".to_string();
+            params.items.push(item);
+        }
+        params
+    }
+
+    fn response(status: bool, response_code: &str) -> R<FundTransferResponseData> {
+        R {
+            status,
+            response_code: Some(response_code.into()),
+            message: "Transaction successful".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bulk_transfer_outcome() {
+        match BulkTransferOutcome::from_response(response(true, "00")) {
+            BulkTransferOutcome::Success(_) => {}
+            other => panic!("{:?}", other),
+        }
+        match BulkTransferOutcome::from_response(response(true, "-1")) {
+            BulkTransferOutcome::Pending(_) => {}
+            other => panic!("{:?}", other),
+        }
+        match BulkTransferOutcome::from_response(response(false, "06")) {
+            BulkTransferOutcome::Failed(Error::Kuda(_)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_send() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        server.fail("SINGLE_FUND_TRANSFER", 503, "Service unavailable");
+        server.respond("SINGLE_FUND_TRANSFER", response(true, "01"));
+        server.respond(
+            "SINGLE_FUND_TRANSFER",
+            R::<FundTransferResponseData> {
+                response_code: None,
+                ..response(true, "00")
+            },
+        );
+
+        let report = BulkTransfer::send(&client, request(&[100, 100, 100, 100, 5000], 1))
+            .await
+            .unwrap();
+        let outcomes = &report.results;
+        match outcomes[0].outcome {
+            BulkTransferOutcome::Pending(Err(_)) => {}
+            ref other => panic!("{:?}", other),
+        }
+        match outcomes[1].outcome {
+            BulkTransferOutcome::Pending(Ok(ref response)) => {
+                assert_eq!(response.response_code, Some(ResponseCode::StatusUnknown))
+            }
+            ref other => panic!("{:?}", other),
+        }
+        match outcomes[2].outcome {
+            BulkTransferOutcome::Pending(Ok(ref response)) => {
+                assert_eq!(response.response_code, None)
+            }
+            ref other => panic!("{:?}", other),
+        }
+        match outcomes[3].outcome {
+            BulkTransferOutcome::Success(_) => {}
+            ref other => panic!("{:?}", other),
+        }
+        match outcomes[4].outcome {
+            BulkTransferOutcome::Failed(Error::Kuda(ref err)) => {
+                assert_eq!(err.response_code, Some(ResponseCode::InsufficientFunds))
+            }
+            ref other => panic!("{:?}", other),
+        }
+        assert_eq!(report.succeeded().count(), 1);
+        assert_eq!(report.pending().count(), 3);
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(server.main_balance(), Money::from_naira(900));
+
+        let transfers: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.service_type == "SINGLE_FUND_TRANSFER")
+            .collect();
+        assert_eq!(transfers.len(), 5);
+        for (result, request) in outcomes.iter().zip(&transfers) {
+            assert_eq!(
+                result.request_reference.as_deref(),
+                Some(request.request_ref.as_str())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_send_concurrency() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        for _ in 0..6 {
+            server.delay("NAME_ENQUIRY", Duration::from_millis(100));
+            server.delay("SINGLE_FUND_TRANSFER", Duration::from_millis(100));
+        }
+
+        let report = BulkTransfer::send(&client, request(&[100; 6], 2))
+            .await
+            .unwrap();
+        assert_eq!(report.succeeded().count(), 6);
+        let references: Vec<_> = report
+            .results
+            .iter()
+            .map(|result| result.item.reference.as_str())
+            .collect();
+        assert_eq!(
+            references,
+            vec!["payout-0", "payout-1", "payout-2", "payout-3", "payout-4", "payout-5"]
+        );
+        assert!(server.max_concurrent_requests() <= 2);
+        assert_eq!(server.main_balance(), Money::from_naira(400));
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::bulk::*;
+pub use self::transfer::*;
+
+mod bulk;
+mod transfer;
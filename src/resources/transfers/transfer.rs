@@ -0,0 +1,588 @@
+use std::time::Duration;
+
+use crate::config::{Client, Response};
+use crate::error::{Error, HttpError, RequestError};
+use crate::money::Money;
+use crate::params::{Response as R, ResponseCode};
+use crate::resources::{
+    AccountEnquiryRequest, AccountEnquiryResponseData, AdminAccount,
+    AdminAccountCheckTransferRequest, AdminAccountFundTransferRequest, FundTransferResponseData,
+    TransferSource, TransferStatus, TransferStatusResponseData, VirtualAccountFundTransferRequest,
+};
+
+/// Time between status checks when `TransferRequest::poll_interval` is unset.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long status is checked for when `TransferRequest::deadline` is unset.
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(120);
+
+/// Kuda's own bank code; transfers to it are not third party bank transfers.
+const KUDA_BANK_CODE: &str = "999129";
+
+pub struct Transfers {}
+
+/// The parameters for `Transfers::send`.
+#[derive(Default, Clone, Debug)]
+pub struct TransferRequest {
+    pub source: TransferSource,
+    /// Destination bank account number.
+    pub beneficiary_account: String,
+    /// Destination bank code.
+    pub beneficiary_bank_code: String,
+    /// The name the destination account is expected to have. The transfer is
+    /// only made if the name enquiry returns the same name; left empty, any
+    /// name is accepted.
+    pub beneficiary_name: String,
+    /// Amount to be transferred. All amounts in kobo.
+    pub amount: Money,
+    /// Transaction narration.
+    pub narration: String,
+    /// Name of the person sending money.
+    pub sender_name: String,
+    /// Time between status checks. Defaults to 5 seconds.
+    pub poll_interval: Duration,
+    /// How long after the transfer its status is checked for. Defaults to 2 minutes.
+    pub deadline: Duration,
+}
+
+impl TransferRequest {
+    pub fn new() -> Self {
+        TransferRequest {
+            source: Default::default(),
+            beneficiary_account: Default::default(),
+            beneficiary_bank_code: Default::default(),
+            beneficiary_name: Default::default(),
+            amount: Default::default(),
+            narration: Default::default(),
+            sender_name: Default::default(),
+            poll_interval: Default::default(),
+            deadline: Default::default(),
+        }
+    }
+}
+
+/// A transfer that was sent, and what became of it.
+#[derive(Debug, Clone)]
+pub struct TransferReceipt {
+    /// The beneficiary's name, as returned by the name enquiry.
+    pub beneficiary_name: String,
+    /// NIBSS session ID of the name enquiry.
+    pub session_id: String,
+    /// The `requestRef` the transfer was sent with.
+    pub request_reference: Option<String>,
+    pub transaction_reference: Option<String>,
+    /// The last status Kuda reported for the transfer. Still pending, or
+    /// `None`, if the deadline passed before the transfer settled.
+    pub status: Option<TransferStatus>,
+    /// The code of the last response about the transfer.
+    pub response_code: Option<ResponseCode>,
+    pub message: String,
+}
+
+impl TransferReceipt {
+    /// Whether the transfer reached the beneficiary.
+    pub fn is_successful(&self) -> bool {
+        self.status
+            .as_ref()
+            .is_some_and(TransferStatus::is_successful)
+    }
+
+    /// Whether the transfer had not settled by the deadline.
+    pub fn is_pending(&self) -> bool {
+        !self.status.as_ref().is_some_and(TransferStatus::is_final)
+    }
+}
+
+impl Transfers {
+    /// Send Money to a beneficiary and wait for the transfer to settle.
+    ///
+    /// The beneficiary is confirmed with a name enquiry, the transfer is made
+    /// with its session ID, and the transfer's status is then checked every
+    /// `poll_interval` until it is final or `deadline` has passed.
+    ///
+    /// Once the transfer has been sent the receipt is always returned, even
+    /// if its response is lost, Kuda answers `status: false` without a final
+    /// code, or status checks fail, so that a transfer which may have gone
+    /// through is never reported as an error. Such a receipt is pending until
+    /// a status check says otherwise, and carries the `requestRef` to look the
+    /// transfer up by. Only a final failure code is returned as `Error::Kuda`.
+    ///
+    /// Async only: waiting between status checks needs the async [`Client`]
+    /// rather than any `KudaTransport`. With the blocking client, run it
+    /// through `blocking::Client::call`.
+    pub fn send(client: &Client, params: TransferRequest) -> Response<TransferReceipt> {
+        let client = client.clone();
+        Box::pin(async move {
+            let enquiry = enquire(
+                &client,
+                &params.beneficiary_account,
+                &params.beneficiary_bank_code,
+            )
+            .await?;
+            if !params.beneficiary_name.is_empty()
+                && !names_match(&params.beneficiary_name, &enquiry.beneficiary_name)
+            {
+                return Err(Error::BeneficiaryMismatch(enquiry.beneficiary_name));
+            }
+
+            let beneficiary_name = enquiry.beneficiary_name.clone();
+            let session_id = enquiry.session_id.clone();
+            let request_reference = client.request_ref();
+            let sent = transfer(
+                &client,
+                &params.source,
+                &params.sender_name,
+                enquiry,
+                params.amount,
+                &params.narration,
+                request_reference.clone(),
+            )
+            .await;
+
+            let mut receipt = match sent {
+                // Kuda may answer `status: false` while the outcome is still
+                // unknown, so only a final failure is an error; anything else
+                // is settled by the transfer's status.
+                Ok(response)
+                    if !response.status
+                        && response
+                            .response_code
+                            .as_ref()
+                            .is_some_and(|code| code.is_final() && !code.is_success()) =>
+                {
+                    return Err(RequestError::rejected(&response).into());
+                }
+                Ok(response) => TransferReceipt {
+                    beneficiary_name,
+                    session_id,
+                    request_reference: Some(request_reference.clone()),
+                    transaction_reference: response.transaction_reference,
+                    status: response.data.and_then(|data| data.status),
+                    response_code: response.response_code,
+                    message: response.message,
+                },
+                Err(error) if !was_sent(&error) => return Err(error),
+                // The transfer may have gone through even though its response
+                // was lost, so find out from its status rather than failing.
+                Err(error) => TransferReceipt {
+                    beneficiary_name,
+                    session_id,
+                    request_reference: Some(request_reference.clone()),
+                    transaction_reference: None,
+                    status: None,
+                    response_code: None,
+                    message: error.to_string(),
+                },
+            };
+
+            let mut status = AdminAccountCheckTransferRequest::new();
+            status.is_third_party_bank_transfer = params.beneficiary_bank_code != KUDA_BANK_CODE;
+            status.transaction_request_reference = request_reference;
+            poll(&client, status, &params, &mut receipt).await;
+
+            Ok(receipt)
+        })
+    }
+}
+
+/// Checks the status of a transfer until it is final or the deadline passes.
+///
+/// The status is checked at least once, even if the deadline is shorter than
+/// the poll interval. Only the `TransferStatus` in a reply settles the
+/// transfer: its response code says whether the query succeeded, not the
+/// transfer.
+async fn poll(
+    client: &Client,
+    status: AdminAccountCheckTransferRequest,
+    params: &TransferRequest,
+    receipt: &mut TransferReceipt,
+) {
+    let interval = match params.poll_interval {
+        Duration::ZERO => DEFAULT_POLL_INTERVAL,
+        interval => interval,
+    };
+    let deadline = match params.deadline {
+        Duration::ZERO => DEFAULT_DEADLINE,
+        deadline => deadline,
+    };
+    let mut wait = interval.min(deadline);
+    let deadline = tokio::time::Instant::now() + deadline;
+
+    loop {
+        tokio::time::sleep(wait).await;
+        if let Some(response) = check(client, status.clone()).await {
+            if let Some(data) = response.data {
+                if receipt.transaction_reference.is_none() {
+                    receipt.transaction_reference = data.transaction_reference;
+                }
+                if let Some(status) = data.status {
+                    let settled = status.is_final();
+                    receipt.status = Some(status);
+                    receipt.response_code = response.response_code;
+                    receipt.message = response.message;
+                    if settled {
+                        return;
+                    }
+                }
+            }
+        }
+
+        if tokio::time::Instant::now() + interval > deadline {
+            return;
+        }
+        wait = interval;
+    }
+}
+
+/// Queries the status of a transfer, or `None` if the query failed.
+async fn check(
+    client: &Client,
+    status: AdminAccountCheckTransferRequest,
+) -> Option<R<TransferStatusResponseData>> {
+    // A failed check says nothing about the transfer.
+    match AdminAccount::check_transfer_status(client, status).await {
+        Ok(response) if response.status => Some(response),
+        _ => None,
+    }
+}
+
+/// Whether a transfer that failed with `error` may have reached Kuda, rather
+/// than failing before it was sent.
+pub(super) fn was_sent(error: &Error) -> bool {
+    !matches!(
+        *error,
+        Error::Serialize(_)
+            | Error::Crypto(_)
+            | Error::InvalidKey(_)
+            | Error::InvalidConfig(_)
+            | Error::Unsupported(_)
+            | Error::Http(HttpError::Request(_))
+    )
+}
+
+/// Confirms a beneficiary account with a name enquiry.
+pub(super) async fn enquire(
+    client: &Client,
+    account: &str,
+    bank_code: &str,
+) -> Result<AccountEnquiryResponseData, Error> {
+    let mut params = AccountEnquiryRequest::new();
+    params.beneficiary_account_number = account.to_string();
+    params.beneficiary_bank_code = bank_code.to_string();
+
+    let response = AdminAccount::enquire_bank_account(client, params).await?;
+    match response.data {
+        Some(ref data) if response.status => Ok(data.clone()),
+        _ => Err(RequestError::rejected(&response).into()),
+    }
+}
+
+/// Transfers `amount` from `source` to the account confirmed by `enquiry`,
+/// with `request_reference` as the transfer's `requestRef`.
+pub(super) async fn transfer(
+    client: &Client,
+    source: &TransferSource,
+    sender_name: &str,
+    enquiry: AccountEnquiryResponseData,
+    amount: Money,
+    narration: &str,
+    request_reference: String,
+) -> Result<R<FundTransferResponseData>, Error> {
+    match *source {
+        TransferSource::MainAccount => {
+            let mut params = AdminAccountFundTransferRequest::new();
+            params.beneficiary_account = enquiry.beneficiary_account_number;
+            params.beneficiary_bank_code = enquiry.beneficiary_bank_code;
+            params.beneficiary_name = enquiry.beneficiary_name;
+            params.amount = amount;
+            params.narration = narration.to_string();
+            params.name_enquiry_session_id = enquiry.session_id;
+            params.sender_name = sender_name.to_string();
+            client
+                .post_body_with_ref("/v1", "SINGLE_FUND_TRANSFER", request_reference, params)
+                .await
+        }
+        TransferSource::VirtualAccount(ref tracking_reference) => {
+            let mut params = VirtualAccountFundTransferRequest::new();
+            params.tracking_reference = tracking_reference.clone();
+            params.beneficiary_account = enquiry.beneficiary_account_number;
+            params.beneficiary_bank_code = enquiry.beneficiary_bank_code;
+            params.beneficiary_name = enquiry.beneficiary_name;
+            params.amount = amount;
+            params.narration = narration.to_string();
+            params.name_enquiry_id = enquiry.session_id;
+            params.sender_name = sender_name.to_string();
+            client
+                .post_body_with_ref(
+                    "/v1",
+                    "VIRTUAL_ACCOUNT_FUND_TRANSFER",
+                    request_reference,
+                    Some(params),
+                )
+                .await
+        }
+    }
+}
+
+/// Whether two account names have the same words, ignoring case, punctuation
+/// and order, since banks differ on whether surnames come first.
+fn names_match(expected: &str, actual: &str) -> bool {
+    fn words(name: &str) -> Vec<String> {
+        let mut words: Vec<String> = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        words.sort();
+        words
+    }
+
+    words(expected) == words(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use crate::Timeouts;
+
+    fn request(server: &MockServer) -> TransferRequest {
+        let account_number = server.create_virtual_account("0012", Money::ZERO);
+
+        let mut params = TransferRequest::new();
+        params.beneficiary_account = account_number;
+        params.beneficiary_bank_code = "000013".to_string();
+        params.beneficiary_name = "customer, MOCK".to_string();
+        params.amount = Money::from_naira(100);
+        params.narration = "Payout".to_string();
+        params.sender_name = "SeamPay".to_string();
+        // A generous deadline: a status check can take longer than 100ms in
+        // debug builds with some encryption backends.
+        params.poll_interval = Duration::from_millis(10);
+        params.deadline = Duration::from_secs(30);
+        params
+    }
+
+    fn status(
+        response_code: ResponseCode,
+        status: TransferStatus,
+    ) -> R<TransferStatusResponseData> {
+        R {
+            status: true,
+            response_code: Some(response_code),
+            message: format!("Transaction {}", status),
+            data: Some(TransferStatusResponseData {
+                status: Some(status),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn pending() -> R<TransferStatusResponseData> {
+        status(ResponseCode::StatusUnknown, TransferStatus::Pending)
+    }
+
+    #[test]
+    fn test_names_match() {
+        assert!(names_match("Mark Smith", "SMITH  MARK"));
+        assert!(names_match("Mark O. Smith", "mark o smith"));
+        assert!(!names_match("Mark Smith", "Mark Smithson"));
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        server.respond("TRANSACTION_STATUS_QUERY", pending());
+
+        let receipt = Transfers::send(&client, request(&server)).await.unwrap();
+        assert!(receipt.is_successful());
+        assert_eq!(receipt.beneficiary_name, "Mock Customer");
+        assert_eq!(server.main_balance(), Money::from_naira(900));
+
+        let requests = server.requests();
+        let service_types: Vec<_> = requests
+            .iter()
+            .map(|request| request.service_type.as_str())
+            .collect();
+        assert_eq!(
+            service_types,
+            vec![
+                "NAME_ENQUIRY",
+                "SINGLE_FUND_TRANSFER",
+                "TRANSACTION_STATUS_QUERY",
+                "TRANSACTION_STATUS_QUERY"
+            ]
+        );
+        let status = requests[3].data.as_ref().unwrap();
+        assert_eq!(
+            status["transactionRequestReference"],
+            requests[1].request_ref.as_str()
+        );
+        assert_eq!(status["isThirdPartyBankTransfer"], true);
+    }
+
+    #[tokio::test]
+    async fn test_send_response_lost() {
+        let server = MockServer::start().await;
+        let client = server.client().with_timeouts(Timeouts {
+            request: Some(Duration::from_secs(2)),
+            ..Default::default()
+        });
+        server.set_main_balance(Money::from_naira(1000));
+        server.delay("SINGLE_FUND_TRANSFER", Duration::from_secs(5));
+
+        let receipt = Transfers::send(&client, request(&server)).await.unwrap();
+        let requests = server.requests();
+        assert_eq!(
+            receipt.request_reference.as_deref(),
+            Some(requests[1].request_ref.as_str())
+        );
+        assert!(receipt.is_successful());
+        assert_eq!(server.main_balance(), Money::from_naira(900));
+        assert_eq!(requests[2].service_type, "TRANSACTION_STATUS_QUERY");
+        assert_eq!(
+            requests[2].data.as_ref().unwrap()["transactionRequestReference"],
+            requests[1].request_ref.as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_checks_status_once() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+
+        let mut params = request(&server);
+        params.poll_interval = Duration::from_secs(60);
+        params.deadline = Duration::from_millis(10);
+        let receipt = Transfers::send(&client, params).await.unwrap();
+        assert!(receipt.is_successful());
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_checks_beneficiary_name() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+
+        let mut params = request(&server);
+        params.beneficiary_name = "Jane Doe".to_string();
+        match Transfers::send(&client, params).await {
+            Err(Error::BeneficiaryMismatch(name)) => assert_eq!(name, "Mock Customer"),
+            other => panic!("expected Error::BeneficiaryMismatch, got {:?}", other),
+        }
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(server.main_balance(), Money::from_naira(1000));
+    }
+
+    #[tokio::test]
+    async fn test_send_rejected() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::ZERO);
+
+        match Transfers::send(&client, request(&server)).await {
+            Err(Error::Kuda(err)) => {
+                assert_eq!(err.response_code, Some(ResponseCode::InsufficientFunds))
+            }
+            other => panic!("expected Error::Kuda, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_deadline() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        server.fail("TRANSACTION_STATUS_QUERY", 503, "Service unavailable");
+        for _ in 0..20 {
+            server.respond("TRANSACTION_STATUS_QUERY", pending());
+        }
+
+        // At most 10 checks fit before the deadline, all of them scripted.
+        let mut params = request(&server);
+        params.poll_interval = Duration::from_millis(100);
+        params.deadline = Duration::from_secs(1);
+        let receipt = Transfers::send(&client, params).await.unwrap();
+        assert!(receipt.is_pending());
+        assert_eq!(receipt.response_code, Some(ResponseCode::StatusUnknown));
+        assert!(receipt.transaction_reference.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_send_failed_status() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        // The query itself succeeded, but the transfer did not.
+        server.respond(
+            "TRANSACTION_STATUS_QUERY",
+            status(ResponseCode::Success, TransferStatus::Failed),
+        );
+
+        let receipt = Transfers::send(&client, request(&server)).await.unwrap();
+        assert!(!receipt.is_successful());
+        assert!(!receipt.is_pending());
+        assert_eq!(receipt.status, Some(TransferStatus::Failed));
+        assert_eq!(receipt.response_code, Some(ResponseCode::Success));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_processing_status() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        server.respond(
+            "TRANSACTION_STATUS_QUERY",
+            status(ResponseCode::Success, TransferStatus::from("Processing")),
+        );
+
+        let receipt = Transfers::send(&client, request(&server)).await.unwrap();
+        assert!(receipt.is_successful());
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_send_rejected_pending() {
+        let server = MockServer::start().await;
+        let client = server.client();
+        server.set_main_balance(Money::from_naira(1000));
+        server.respond(
+            "SINGLE_FUND_TRANSFER",
+            R::<FundTransferResponseData> {
+                transaction_reference: Some("210528024512893245611209873".to_string()),
+                response_code: Some(ResponseCode::StatusUnknown),
+                status: false,
+                message: "Transaction pending".to_string(),
+                ..Default::default()
+            },
+        );
+        server.respond(
+            "TRANSACTION_STATUS_QUERY",
+            status(ResponseCode::Success, TransferStatus::Successful),
+        );
+
+        let receipt = Transfers::send(&client, request(&server)).await.unwrap();
+        assert!(receipt.is_successful());
+        assert_eq!(
+            receipt.transaction_reference.as_deref(),
+            Some("210528024512893245611209873")
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            receipt.request_reference.as_deref(),
+            Some(requests[1].request_ref.as_str())
+        );
+        assert_eq!(
+            requests[2].data.as_ref().unwrap()["transactionRequestReference"],
+            requests[1].request_ref.as_str()
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Notifications pushed by Kuda to the merchant callback URL.
+//!
+//! Use [`parse`] to decode a raw request body, or mount a [`WebhookService`]
+//! on a hyper server to receive notifications directly.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{Method, StatusCode};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::money::Money;
+use crate::params::ResponseCode;
+
+/// A notification received from Kuda, keyed by its `transactionType`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "transactionType")]
+pub enum Notification {
+    /// Funds were received into the main account or a virtual account.
+    Credit(CreditNotification),
+    /// An outward transfer has completed, successfully or not.
+    Debit(TransferNotification),
+    /// A previous outward transfer has been reversed.
+    Reversal(ReversalNotification),
+}
+
+impl Notification {
+    /// Kuda's reference for the transaction.
+    pub fn transaction_reference(&self) -> &str {
+        match *self {
+            Notification::Credit(ref n) => &n.transaction_reference,
+            Notification::Debit(ref n) => &n.transaction_reference,
+            Notification::Reversal(ref n) => &n.transaction_reference,
+        }
+    }
+
+    /// The account the notification applies to.
+    pub fn account_number(&self) -> &str {
+        match *self {
+            Notification::Credit(ref n) => &n.account_number,
+            Notification::Debit(ref n) => &n.account_number,
+            Notification::Reversal(ref n) => &n.account_number,
+        }
+    }
+
+    /// The transaction amount.
+    pub fn amount(&self) -> Money {
+        match *self {
+            Notification::Credit(ref n) => n.amount,
+            Notification::Debit(ref n) => n.amount,
+            Notification::Reversal(ref n) => n.amount,
+        }
+    }
+}
+
+/// An inbound credit to an account.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditNotification {
+    /// Name of the bank the funds were sent from.
+    pub paying_bank: Option<String>,
+    /// Amount received. All amounts in kobo.
+    pub amount: Money,
+    pub transaction_reference: String,
+    pub transaction_date: String,
+    pub narrations: Option<String>,
+    pub account_name: Option<String>,
+    /// Account number that was credited.
+    pub account_number: String,
+    /// Name of the person sending money.
+    pub sender_name: Option<String>,
+    pub recipient_name: Option<String>,
+    pub instrument_number: Option<String>,
+    /// NIBSS session ID of the transfer.
+    #[serde(rename = "SessionId", alias = "sessionId")]
+    pub session_id: Option<String>,
+    /// Tracking reference of the credited virtual account, if any.
+    pub client_request_ref: Option<String>,
+}
+
+/// The outcome of an outward transfer.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferNotification {
+    /// Amount transferred. All amounts in kobo.
+    pub amount: Money,
+    pub transaction_reference: String,
+    pub transaction_date: String,
+    pub narrations: Option<String>,
+    pub account_name: Option<String>,
+    /// Account number that was debited.
+    pub account_number: String,
+    /// Name of the person sending money.
+    pub sender_name: Option<String>,
+    /// Name of the person receiving money.
+    pub recipient_name: Option<String>,
+    pub beneficiary_bank: Option<String>,
+    /// NIBSS session ID of the transfer.
+    #[serde(rename = "SessionId", alias = "sessionId")]
+    pub session_id: Option<String>,
+    /// The `requestRef` the transfer was sent with.
+    pub client_request_ref: Option<String>,
+    /// `ResponseCode::Success` when the transfer succeeded.
+    pub response_code: Option<ResponseCode>,
+    pub message: Option<String>,
+}
+
+impl TransferNotification {
+    /// Whether the transfer reached the beneficiary.
+    pub fn is_successful(&self) -> bool {
+        self.response_code == Some(ResponseCode::Success)
+    }
+}
+
+/// The reversal of an outward transfer.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReversalNotification {
+    /// Amount returned to the account. All amounts in kobo.
+    pub amount: Money,
+    pub transaction_reference: String,
+    pub transaction_date: String,
+    pub narrations: Option<String>,
+    pub account_name: Option<String>,
+    /// Account number that was refunded.
+    pub account_number: String,
+    /// Reference of the transfer being reversed.
+    pub original_transaction_reference: Option<String>,
+    /// NIBSS session ID of the reversed transfer.
+    #[serde(rename = "SessionId", alias = "sessionId")]
+    pub session_id: Option<String>,
+    /// The `requestRef` the reversed transfer was sent with.
+    pub client_request_ref: Option<String>,
+}
+
+/// Decode and validate the raw body of a webhook request.
+#[allow(clippy::result_large_err)]
+pub fn parse(body: &[u8]) -> Result<Notification, Error> {
+    let notification: Notification = serde_json::from_slice(body).map_err(Error::deserialize)?;
+
+    if notification.transaction_reference().is_empty() {
+        return Err(Error::InvalidNotification("missing transaction reference"));
+    }
+    if notification.account_number().is_empty() {
+        return Err(Error::InvalidNotification("missing account number"));
+    }
+    if notification.amount().is_negative() {
+        return Err(Error::InvalidNotification("amount is negative"));
+    }
+
+    Ok(notification)
+}
+
+/// A hyper service that parses webhook requests and hands the notifications
+/// to a callback.
+///
+/// Responds `200` once the callback succeeds, `400` for malformed bodies,
+/// `405` for anything but `POST`, and `500` if the callback fails so that
+/// Kuda retries the notification.
+///
+/// ```ignore
+/// let service = WebhookService::new(|notification| async move {
+///     println!("{:?}", notification);
+///     Ok(())
+/// });
+/// let make_service = make_service_fn(move |_| {
+///     let service = service.clone();
+///     async move { Ok::<_, Infallible>(service) }
+/// });
+/// hyper::Server::bind(&addr).serve(make_service).await?;
+/// ```
+#[derive(Clone)]
+pub struct WebhookService<F> {
+    callback: F,
+}
+
+impl<F, Fut> WebhookService<F>
+where
+    F: Fn(Notification) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Result<(), Error>> + Send + 'static,
+{
+    pub fn new(callback: F) -> Self {
+        WebhookService { callback }
+    }
+}
+
+impl<F, Fut> hyper::service::Service<hyper::Request<hyper::Body>> for WebhookService<F>
+where
+    F: Fn(Notification) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Result<(), Error>> + Send + 'static,
+{
+    type Response = hyper::Response<hyper::Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: hyper::Request<hyper::Body>) -> Self::Future {
+        let callback = self.callback.clone();
+        Box::pin(async move {
+            if request.method() != Method::POST {
+                return Ok(respond(StatusCode::METHOD_NOT_ALLOWED));
+            }
+
+            let body = match hyper::body::to_bytes(request.into_body()).await {
+                Err(_) => return Ok(respond(StatusCode::BAD_REQUEST)),
+                Ok(body) => body,
+            };
+            let notification = match parse(&body) {
+                Err(_) => return Ok(respond(StatusCode::BAD_REQUEST)),
+                Ok(notification) => notification,
+            };
+
+            match callback(notification).await {
+                Err(_) => Ok(respond(StatusCode::INTERNAL_SERVER_ERROR)),
+                Ok(()) => Ok(respond(StatusCode::OK)),
+            }
+        })
+    }
+}
+
+fn respond(status: StatusCode) -> hyper::Response<hyper::Body> {
+    let mut response = hyper::Response::new(hyper::Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::Service;
+
+    const CREDIT: &str = include_str!("../tests/fixtures/webhooks/credit.json");
+    const TRANSFER_SUCCESS: &str = include_str!("../tests/fixtures/webhooks/transfer_success.json");
+    const TRANSFER_FAILED: &str = include_str!("../tests/fixtures/webhooks/transfer_failed.json");
+    const REVERSAL: &str = include_str!("../tests/fixtures/webhooks/reversal.json");
+
+    #[test]
+    fn test_parse_credit() {
+        let credit = match parse(CREDIT.as_bytes()) {
+            Ok(Notification::Credit(credit)) => credit,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(credit.amount, Money::from_kobo(250000));
+        assert_eq!(credit.account_number, "2000072149");
+        assert_eq!(credit.client_request_ref.as_deref(), Some("0012"));
+        assert_eq!(
+            credit.session_id.as_deref(),
+            Some("000013210528102100021245478901")
+        );
+    }
+
+    #[test]
+    fn test_parse_transfer() {
+        match parse(TRANSFER_SUCCESS.as_bytes()) {
+            Ok(Notification::Debit(transfer)) => assert!(transfer.is_successful()),
+            other => panic!("{:?}", other),
+        }
+
+        match parse(TRANSFER_FAILED.as_bytes()) {
+            Ok(Notification::Debit(transfer)) => {
+                assert!(!transfer.is_successful());
+                assert_eq!(
+                    transfer.message.as_deref(),
+                    Some("Beneficiary bank not available")
+                );
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_reversal() {
+        let reversal = match parse(REVERSAL.as_bytes()) {
+            Ok(Notification::Reversal(reversal)) => reversal,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(
+            reversal.original_transaction_reference.as_deref(),
+            Some("210528024833451276540981234")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        match parse(b"{\"transactionType\":\"Credit\"}") {
+            Err(Error::Deserialize(_)) => {}
+            other => panic!("{:?}", other),
+        }
+
+        let missing_reference = CREDIT.replace("210528010210367425698734566", "");
+        match parse(missing_reference.as_bytes()) {
+            Err(Error::InvalidNotification(_)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_service() {
+        let mut service = WebhookService::new(|notification: Notification| async move {
+            match notification {
+                Notification::Credit(_) => Ok(()),
+                _ => Err(Error::Unexpected("not a credit")),
+            }
+        });
+
+        let request = |body: &'static str| {
+            hyper::Request::post("/webhooks/kuda")
+                .body(hyper::Body::from(body))
+                .unwrap()
+        };
+
+        let response = service.call(request(CREDIT)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = service.call(request(REVERSAL)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let response = service.call(request("not json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}